@@ -20,6 +20,37 @@ pub enum EventRegistryError {
     TierNotFound = 14,
     TierSupplyExceeded = 15,
     SupplyUnderflow = 16,
+    InvalidQuantity = 17,
+    InvalidRefundSchedule = 18,
+    InvalidEventTime = 19,
+    InvalidResalePolicy = 20,
+    InvalidSalesWindow = 21,
+    SalesNotStarted = 22,
+    SalesEnded = 23,
+    InvalidPriceSchedule = 24,
+    TierAlreadyExists = 25,
+    TierLimitBelowSold = 26,
+    TierRetired = 27,
+    SupplyMismatch = 28,
+    InvalidStatusTransition = 29,
+    InvalidRole = 30,
+    RoleNotFound = 31,
+    TransferNotFound = 32,
+    NoPendingAdmin = 33,
+    ContractPaused = 34,
+    FeeOverrideNotFound = 35,
+    ReservationNotFound = 36,
+    TicketsAvailable = 37,
+    AlreadyOnWaitlist = 38,
+    NotOnWaitlist = 39,
+    InvalidDiscount = 40,
+    PromoCodeAlreadyExists = 41,
+    PromoCodeNotFound = 42,
+    PromoCodeExpired = 43,
+    PromoCodeNotApplicable = 44,
+    PromoCodeExhausted = 45,
+    InvalidAccessRule = 46,
+    PriceStepCrossed = 47,
 }
 
 impl core::fmt::Display for EventRegistryError {
@@ -59,6 +90,58 @@ impl core::fmt::Display for EventRegistryError {
             EventRegistryError::SupplyUnderflow => {
                 write!(f, "Supply counter underflow")
             }
+            EventRegistryError::InvalidQuantity => {
+                write!(f, "Ticket quantity must be greater than zero")
+            }
+            EventRegistryError::InvalidRefundSchedule => {
+                write!(
+                    f,
+                    "Refund schedule deadlines must increase and refunds cannot exceed 100%"
+                )
+            }
+            EventRegistryError::InvalidEventTime => {
+                write!(f, "Event end time must be in the future")
+            }
+            EventRegistryError::InvalidResalePolicy => {
+                write!(f, "Resale royalty cannot exceed 100%")
+            }
+            EventRegistryError::InvalidSalesWindow => write!(f, "Invalid tier sales window"),
+            EventRegistryError::SalesNotStarted => write!(f, "Ticket sales have not started"),
+            EventRegistryError::SalesEnded => write!(f, "Ticket sales have ended"),
+            EventRegistryError::InvalidPriceSchedule => write!(f, "Invalid tier price schedule"),
+            EventRegistryError::TierAlreadyExists => write!(f, "Tier already exists"),
+            EventRegistryError::TierLimitBelowSold => {
+                write!(f, "Tier limit cannot be below tickets sold")
+            }
+            EventRegistryError::TierRetired => write!(f, "Tier has been retired"),
+            EventRegistryError::SupplyMismatch => {
+                write!(f, "Event supply does not match tier sales")
+            }
+            EventRegistryError::InvalidStatusTransition => {
+                write!(f, "Event status transition not allowed")
+            }
+            EventRegistryError::InvalidRole => write!(f, "Role cannot be granted"),
+            EventRegistryError::RoleNotFound => write!(f, "Role not found"),
+            EventRegistryError::TransferNotFound => write!(f, "No pending event transfer"),
+            EventRegistryError::NoPendingAdmin => write!(f, "No pending admin"),
+            EventRegistryError::ContractPaused => write!(f, "Contract is paused"),
+            EventRegistryError::FeeOverrideNotFound => write!(f, "Fee override not found"),
+            EventRegistryError::ReservationNotFound => write!(f, "Reservation not found"),
+            EventRegistryError::TicketsAvailable => write!(f, "Tickets are still available"),
+            EventRegistryError::AlreadyOnWaitlist => write!(f, "Buyer is already on the waitlist"),
+            EventRegistryError::NotOnWaitlist => write!(f, "Buyer is not on the waitlist"),
+            EventRegistryError::InvalidDiscount => write!(f, "Invalid discount"),
+            EventRegistryError::PromoCodeAlreadyExists => write!(f, "Promo code already exists"),
+            EventRegistryError::PromoCodeNotFound => write!(f, "Promo code not found"),
+            EventRegistryError::PromoCodeExpired => write!(f, "Promo code has expired"),
+            EventRegistryError::PromoCodeNotApplicable => {
+                write!(f, "Promo code does not apply to this tier")
+            }
+            EventRegistryError::PromoCodeExhausted => {
+                write!(f, "Promo code redemption limit reached")
+            }
+            EventRegistryError::InvalidAccessRule => write!(f, "Invalid access rule"),
+            EventRegistryError::PriceStepCrossed => write!(f, "Purchase would cross a price step"),
         }
     }
 }
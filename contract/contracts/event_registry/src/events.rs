@@ -1,4 +1,5 @@
-use soroban_sdk::{contracttype, Address, String};
+use crate::types::{AccessRule, EventRole, EventStatus, FeeOverrideTarget};
+use soroban_sdk::{contracttype, Address, BytesN, String};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -11,6 +12,32 @@ pub enum AgoraEvent {
     MetadataUpdated,
     InventoryIncremented,
     InventoryDecremented,
+    ResalePolicyUpdated,
+    TierAdded,
+    TierUpdated,
+    TierRetired,
+    EventImported,
+    RoleGranted,
+    RoleRevoked,
+    EventTransferProposed,
+    EventTransferred,
+    AdminProposed,
+    AdminChanged,
+    PlatformWalletUpdated,
+    ContractPaused,
+    ContractUnpaused,
+    FeeOverrideSet,
+    FeeOverrideRemoved,
+    InventoryReserved,
+    ReservationReleased,
+    WaitlistJoined,
+    WaitlistOffered,
+    PromoCodeAdded,
+    PromoCodeRemoved,
+    PromoCodeRedeemed,
+    PurchaseLimitUpdated,
+    AccessRuleUpdated,
+    AllowlistUpdated,
 }
 
 #[contracttype]
@@ -26,7 +53,8 @@ pub struct EventRegisteredEvent {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EventStatusUpdatedEvent {
     pub event_id: String,
-    pub is_active: bool,
+    pub old_status: EventStatus,
+    pub new_status: EventStatus,
     pub updated_by: Address,
     pub timestamp: u64,
 }
@@ -66,6 +94,7 @@ pub struct MetadataUpdatedEvent {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InventoryIncrementedEvent {
     pub event_id: String,
+    pub quantity: u32,
     pub new_supply: i128,
     pub max_supply: i128,
     pub timestamp: u64,
@@ -75,7 +104,246 @@ pub struct InventoryIncrementedEvent {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InventoryDecrementedEvent {
     pub event_id: String,
+    pub quantity: u32,
     pub new_supply: i128,
     pub max_supply: i128,
     pub timestamp: u64,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResalePolicyUpdatedEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub max_resale_bps: u32,
+    pub royalty_bps: u32,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierAddedEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub price: i128,
+    pub tier_limit: i128,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierUpdatedEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub price: i128,
+    pub tier_limit: i128,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierRetiredEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub current_sold: i128,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventImportedEvent {
+    pub event_id: String,
+    pub organizer_address: Address,
+    pub payment_address: Address,
+    pub platform_fee_percent: u32,
+    pub current_supply: i128,
+    pub imported_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleGrantedEvent {
+    pub event_id: String,
+    pub account: Address,
+    pub role: EventRole,
+    pub granted_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleRevokedEvent {
+    pub event_id: String,
+    pub account: Address,
+    pub role: EventRole,
+    pub revoked_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventTransferProposedEvent {
+    pub event_id: String,
+    pub current_organizer: Address,
+    pub new_organizer: Address,
+    pub new_payment_address: Option<Address>,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventTransferredEvent {
+    pub event_id: String,
+    pub previous_organizer: Address,
+    pub new_organizer: Address,
+    pub payment_address: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposedEvent {
+    pub current_admin: Address,
+    pub proposed_admin: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminChangedEvent {
+    pub previous_admin: Address,
+    pub new_admin: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformWalletUpdatedEvent {
+    pub old_wallet: Address,
+    pub new_wallet: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseStatusEvent {
+    pub reason: u32,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeOverrideSetEvent {
+    pub target: FeeOverrideTarget,
+    pub fee_percent: u32,
+    pub expires_at: Option<u64>,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeOverrideRemovedEvent {
+    pub target: FeeOverrideTarget,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InventoryReservedEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub buyer: Address,
+    pub quantity: u32,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReservationReleasedEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub buyer: Address,
+    pub quantity: u32,
+    pub released_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaitlistJoinedEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub buyer: Address,
+    pub quantity: u32,
+    pub position: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaitlistOfferedEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub buyer: Address,
+    pub quantity: u32,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromoCodeUpdatedEvent {
+    pub event_id: String,
+    pub code_hash: BytesN<32>,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromoCodeRedeemedEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub code_hash: BytesN<32>,
+    pub quantity: u32,
+    pub redemptions: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PurchaseLimitUpdatedEvent {
+    pub event_id: String,
+    pub tier_id: Option<String>,
+    pub max_per_buyer: u32,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessRuleUpdatedEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub access_rule: AccessRule,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowlistUpdatedEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub count: u32,
+    pub allowed: bool,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
@@ -1,11 +1,25 @@
 #![no_std]
+// Argument helpers generated by `#[contractimpl]` mirror the parameter lists of entry
+// points such as `register_event` and do not inherit function-level allows
+#![allow(clippy::too_many_arguments)]
 
 use crate::events::{
-    AgoraEvent, EventRegisteredEvent, EventStatusUpdatedEvent, FeeUpdatedEvent,
-    InitializationEvent, InventoryIncrementedEvent, MetadataUpdatedEvent, RegistryUpgradedEvent,
+    AccessRuleUpdatedEvent, AdminChangedEvent, AdminProposedEvent, AgoraEvent,
+    AllowlistUpdatedEvent, EventImportedEvent, EventRegisteredEvent, EventStatusUpdatedEvent,
+    EventTransferProposedEvent, EventTransferredEvent, FeeOverrideRemovedEvent,
+    FeeOverrideSetEvent, FeeUpdatedEvent, InitializationEvent, InventoryIncrementedEvent,
+    InventoryReservedEvent, MetadataUpdatedEvent, PauseStatusEvent, PlatformWalletUpdatedEvent,
+    PromoCodeRedeemedEvent, PromoCodeUpdatedEvent, PurchaseLimitUpdatedEvent,
+    RegistryUpgradedEvent, ResalePolicyUpdatedEvent, ReservationReleasedEvent, RoleGrantedEvent,
+    RoleRevokedEvent, TierAddedEvent, TierRetiredEvent, TierUpdatedEvent, WaitlistJoinedEvent,
+    WaitlistOfferedEvent,
 };
-use crate::types::{EventInfo, PaymentInfo, TicketTier};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, String, Vec};
+use crate::types::{
+    AccessRule, Discount, EventInfo, EventRole, EventStatus, FeeOverride, FeeOverrideTarget,
+    PaymentInfo, PendingEventTransfer, PriceTrigger, PromoCode, Reservation, TicketTier,
+    TierAvailability, WaitlistEntry,
+};
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Map, String, Vec};
 
 pub mod error;
 pub mod events;
@@ -14,6 +28,9 @@ pub mod types;
 
 use crate::error::EventRegistryError;
 
+/// How long a waitlisted buyer's purchase right lasts once capacity frees up.
+pub const WAITLIST_OFFER_DURATION: u64 = 60 * 60;
+
 #[contract]
 pub struct EventRegistry;
 
@@ -74,6 +91,10 @@ impl EventRegistry {
     /// * `metadata_cid` - IPFS CID for event metadata
     /// * `max_supply` - Maximum number of tickets (0 = unlimited)
     /// * `tiers` - Map of tier_id to TicketTier for multi-tiered pricing
+    /// * `start_time` - Timestamp when the event starts
+    /// * `end_time` - Timestamp when the event ends; must be in the future and after `start_time`
+    /// * `publish` - Whether the event goes on sale right away; otherwise it starts as a
+    ///   `Draft` to be published with `update_event_status`
     pub fn register_event(
         env: Env,
         event_id: String,
@@ -82,10 +103,14 @@ impl EventRegistry {
         metadata_cid: String,
         max_supply: i128,
         tiers: Map<String, TicketTier>,
+        start_time: u64,
+        end_time: u64,
+        publish: bool,
     ) -> Result<(), EventRegistryError> {
         if !storage::is_initialized(&env) {
             return Err(EventRegistryError::NotInitialized);
         }
+        ensure_not_paused(&env)?;
         organizer_address.require_auth();
 
         validate_metadata_cid(&env, &metadata_cid)?;
@@ -94,19 +119,18 @@ impl EventRegistry {
             return Err(EventRegistryError::EventAlreadyExists);
         }
 
-        // Validate tier limits don't exceed max_supply
-        if max_supply > 0 {
-            let mut total_tier_limit: i128 = 0;
-            for tier in tiers.values() {
-                total_tier_limit = total_tier_limit
-                    .checked_add(tier.tier_limit)
-                    .ok_or(EventRegistryError::SupplyOverflow)?;
-            }
-            if total_tier_limit > max_supply {
-                return Err(EventRegistryError::TierLimitExceedsMaxSupply);
-            }
+        if end_time <= env.ledger().timestamp() || start_time >= end_time {
+            return Err(EventRegistryError::InvalidEventTime);
         }
 
+        for tier in tiers.values() {
+            validate_tier(&tier)?;
+        }
+
+        validate_tier_limits(&tiers, max_supply)?;
+
+        // The event keeps the global fee; fee overrides are resolved when payment info is
+        // read, so a time-limited override stops applying once it expires
         let platform_fee_percent = storage::get_platform_fee(&env);
 
         let event_info = EventInfo {
@@ -114,11 +138,18 @@ impl EventRegistry {
             organizer_address: organizer_address.clone(),
             payment_address: payment_address.clone(),
             platform_fee_percent,
-            is_active: true,
+            status: if publish {
+                EventStatus::Published
+            } else {
+                EventStatus::Draft
+            },
             created_at: env.ledger().timestamp(),
+            start_time,
+            end_time,
             metadata_cid,
             max_supply,
             current_supply: 0,
+            max_per_buyer: 0,
             tiers,
         };
 
@@ -137,19 +168,27 @@ impl EventRegistry {
         Ok(())
     }
 
-    /// Get event payment information including tiered pricing
+    /// Get event payment information including tiered pricing. The platform fee
+    /// reflects any active event or organizer fee override.
     pub fn get_event_payment_info(
         env: Env,
         event_id: String,
     ) -> Result<PaymentInfo, EventRegistryError> {
         match storage::get_event(&env, event_id) {
             Some(event_info) => {
-                if !event_info.is_active {
+                if event_info.status != EventStatus::Published {
                     return Err(EventRegistryError::EventInactive);
                 }
+                // Active overrides win over the fee stored at registration
+                let platform_fee_percent = effective_platform_fee(
+                    &env,
+                    &event_info.event_id,
+                    &event_info.organizer_address,
+                    event_info.platform_fee_percent,
+                );
                 Ok(PaymentInfo {
                     payment_address: event_info.payment_address,
-                    platform_fee_percent: event_info.platform_fee_percent,
+                    platform_fee_percent,
                     tiers: event_info.tiers,
                 })
             }
@@ -157,19 +196,31 @@ impl EventRegistry {
         }
     }
 
-    /// Update event status (only by organizer)
+    /// Moves an event to a new lifecycle state (owner or manager)
+    ///
+    /// Allowed transitions:
+    /// * `Draft` -> `Published` | `Cancelled`
+    /// * `Published` -> `SalesPaused` | `Cancelled` | `Completed`
+    /// * `SalesPaused` -> `Published` | `Cancelled` | `Completed`
+    ///
+    /// `Cancelled` and `Completed` are final.
     pub fn update_event_status(
         env: Env,
+        caller: Address,
         event_id: String,
-        is_active: bool,
+        new_status: EventStatus,
     ) -> Result<(), EventRegistryError> {
         match storage::get_event(&env, event_id.clone()) {
             Some(mut event_info) => {
-                // Verify organizer signature
-                event_info.organizer_address.require_auth();
+                require_event_manager(&env, &event_info, &caller)?;
+
+                let old_status = event_info.status;
+                if !is_valid_transition(old_status, new_status) {
+                    return Err(EventRegistryError::InvalidStatusTransition);
+                }
 
                 // Update status
-                event_info.is_active = is_active;
+                event_info.status = new_status;
                 storage::store_event(&env, event_info.clone());
 
                 // Emit status update event using contract event type
@@ -177,8 +228,9 @@ impl EventRegistry {
                     (AgoraEvent::EventStatusUpdated,),
                     EventStatusUpdatedEvent {
                         event_id,
-                        is_active,
-                        updated_by: event_info.organizer_address,
+                        old_status,
+                        new_status,
+                        updated_by: caller,
                         timestamp: env.ledger().timestamp(),
                     },
                 );
@@ -189,16 +241,16 @@ impl EventRegistry {
         }
     }
 
-    /// Update the decentralized metadata CID for an event (only by organizer)
+    /// Update the decentralized metadata CID for an event (owner or manager)
     pub fn update_metadata(
         env: Env,
+        caller: Address,
         event_id: String,
         new_metadata_cid: String,
     ) -> Result<(), EventRegistryError> {
         match storage::get_event(&env, event_id.clone()) {
             Some(mut event_info) => {
-                // Verify organizer signature
-                event_info.organizer_address.require_auth();
+                require_event_manager(&env, &event_info, &caller)?;
 
                 // Validate new metadata CID
                 validate_metadata_cid(&env, &new_metadata_cid)?;
@@ -213,7 +265,7 @@ impl EventRegistry {
                     MetadataUpdatedEvent {
                         event_id,
                         new_metadata_cid,
-                        updated_by: event_info.organizer_address,
+                        updated_by: caller,
                         timestamp: env.ledger().timestamp(),
                     },
                 );
@@ -224,151 +276,281 @@ impl EventRegistry {
         }
     }
 
-    /// Stores or updates an event (legacy function for backward compatibility).
-    pub fn store_event(env: Env, event_info: EventInfo) {
-        // In a real scenario, we would check authorization here.
-        storage::store_event(&env, event_info);
-    }
-
-    /// Retrieves an event by its ID.
-    pub fn get_event(env: Env, event_id: String) -> Option<EventInfo> {
-        storage::get_event(&env, event_id)
-    }
-
-    /// Checks if an event exists.
-    pub fn event_exists(env: Env, event_id: String) -> bool {
-        storage::event_exists(&env, event_id)
-    }
-
-    /// Retrieves all event IDs for an organizer.
-    pub fn get_organizer_events(env: Env, organizer: Address) -> Vec<String> {
-        storage::get_organizer_events(&env, &organizer)
-    }
-
-    /// Updates the platform fee percentage. Only callable by the administrator.
-    pub fn set_platform_fee(env: Env, new_fee_percent: u32) -> Result<(), EventRegistryError> {
-        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
-        admin.require_auth();
+    /// Grants a role on an event (only by the owner)
+    ///
+    /// Managers can update metadata, tiers and status and withdraw funds; scanners can
+    /// only check tickets in. Ownership itself cannot be granted this way.
+    ///
+    /// # Arguments
+    /// * `event_id` - The event to grant the role on.
+    /// * `account` - The address receiving the role.
+    /// * `role` - `Manager` or `Scanner`.
+    pub fn grant_role(
+        env: Env,
+        event_id: String,
+        account: Address,
+        role: EventRole,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        event_info.organizer_address.require_auth();
 
-        if new_fee_percent > 10000 {
-            return Err(EventRegistryError::InvalidFeePercent);
+        if role == EventRole::Owner || account == event_info.organizer_address {
+            return Err(EventRegistryError::InvalidRole);
         }
 
-        storage::set_platform_fee(&env, new_fee_percent);
+        storage::set_event_role(&env, event_id.clone(), &account, role);
 
-        // Emit fee update event using contract event type
         env.events().publish(
-            (AgoraEvent::FeeUpdated,),
-            FeeUpdatedEvent { new_fee_percent },
+            (AgoraEvent::RoleGranted,),
+            RoleGrantedEvent {
+                event_id,
+                account,
+                role,
+                granted_by: event_info.organizer_address,
+                timestamp: env.ledger().timestamp(),
+            },
         );
 
         Ok(())
     }
 
-    /// Returns the current platform fee percentage.
-    pub fn get_platform_fee(env: Env) -> u32 {
-        storage::get_platform_fee(&env)
-    }
+    /// Revokes any role an address holds on an event (only by the owner)
+    pub fn revoke_role(
+        env: Env,
+        event_id: String,
+        account: Address,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        event_info.organizer_address.require_auth();
 
-    /// Returns the current administrator address.
-    pub fn get_admin(env: Env) -> Result<Address, EventRegistryError> {
-        storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)
+        let role = storage::get_event_role(&env, event_id.clone(), &account)
+            .ok_or(EventRegistryError::RoleNotFound)?;
+        storage::remove_event_role(&env, event_id.clone(), &account);
+
+        env.events().publish(
+            (AgoraEvent::RoleRevoked,),
+            RoleRevokedEvent {
+                event_id,
+                account,
+                role,
+                revoked_by: event_info.organizer_address,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
     }
 
-    /// Returns the current platform wallet address.
-    pub fn get_platform_wallet(env: Env) -> Result<Address, EventRegistryError> {
-        storage::get_platform_wallet(&env).ok_or(EventRegistryError::NotInitialized)
+    /// Returns the role an address holds on an event. The organizer is the `Owner`.
+    pub fn get_event_role(env: Env, event_id: String, account: Address) -> Option<EventRole> {
+        let event_info = storage::get_event(&env, event_id.clone())?;
+        if account == event_info.organizer_address {
+            return Some(EventRole::Owner);
+        }
+        storage::get_event_role(&env, event_id, &account)
     }
 
-    /// Sets the authorized TicketPayment contract address. Only callable by the administrator.
+    /// Proposes handing an event over to another organizer (only by the owner)
+    ///
+    /// The transfer takes effect once the new organizer calls `accept_event_transfer`.
+    /// A new proposal replaces any pending one.
     ///
     /// # Arguments
-    /// * `ticket_payment_address` - The address of the TicketPayment contract authorized
-    ///   to call `increment_inventory`.
-    pub fn set_ticket_payment_contract(
+    /// * `event_id` - The event to transfer.
+    /// * `new_organizer` - The organizer who will own the event.
+    /// * `new_payment_address` - New payment address, or `None` to keep the current one.
+    pub fn propose_event_transfer(
         env: Env,
-        ticket_payment_address: Address,
+        event_id: String,
+        new_organizer: Address,
+        new_payment_address: Option<Address>,
     ) -> Result<(), EventRegistryError> {
-        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
-        admin.require_auth();
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        event_info.organizer_address.require_auth();
 
-        validate_address(&env, &ticket_payment_address)?;
+        validate_address(&env, &new_organizer)?;
+        if new_organizer == event_info.organizer_address {
+            return Err(EventRegistryError::InvalidAddress);
+        }
+        if let Some(payment_address) = &new_payment_address {
+            validate_address(&env, payment_address)?;
+        }
+
+        storage::set_pending_event_transfer(
+            &env,
+            event_id.clone(),
+            &PendingEventTransfer {
+                new_organizer: new_organizer.clone(),
+                new_payment_address: new_payment_address.clone(),
+                proposed_at: env.ledger().timestamp(),
+            },
+        );
+
+        env.events().publish(
+            (AgoraEvent::EventTransferProposed,),
+            EventTransferProposedEvent {
+                event_id,
+                current_organizer: event_info.organizer_address,
+                new_organizer,
+                new_payment_address,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
-        storage::set_ticket_payment_contract(&env, &ticket_payment_address);
         Ok(())
     }
 
-    /// Returns the authorized TicketPayment contract address.
-    pub fn get_ticket_payment_contract(env: Env) -> Result<Address, EventRegistryError> {
-        storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)
+    /// Accepts a pending event transfer (only by the proposed organizer)
+    ///
+    /// Moves the event between the organizers' event lists and rotates the payment
+    /// address if one was proposed. Escrowed funds are paid to the event's payment
+    /// address at withdrawal time, so they follow the event to its new owner.
+    pub fn accept_event_transfer(env: Env, event_id: String) -> Result<(), EventRegistryError> {
+        let mut event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        let transfer = storage::get_pending_event_transfer(&env, event_id.clone())
+            .ok_or(EventRegistryError::TransferNotFound)?;
+        transfer.new_organizer.require_auth();
+
+        let previous_organizer = event_info.organizer_address.clone();
+        event_info.organizer_address = transfer.new_organizer.clone();
+        if let Some(payment_address) = transfer.new_payment_address {
+            event_info.payment_address = payment_address;
+        }
+
+        storage::remove_organizer_event(&env, &previous_organizer, &event_id);
+        storage::store_event(&env, event_info.clone());
+        storage::remove_pending_event_transfer(&env, event_id.clone());
+        // Roles delegated by the previous owner do not carry over to the new one
+        storage::clear_event_roles(&env, event_id.clone());
+
+        env.events().publish(
+            (AgoraEvent::EventTransferred,),
+            EventTransferredEvent {
+                event_id,
+                previous_organizer,
+                new_organizer: transfer.new_organizer,
+                payment_address: event_info.payment_address,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
     }
 
-    /// Increments the current_supply counter for a given event and tier.
-    /// This function is restricted to calls from the authorized TicketPayment contract.
+    /// Returns the pending ownership transfer of an event, if any.
+    pub fn get_pending_event_transfer(env: Env, event_id: String) -> Option<PendingEventTransfer> {
+        storage::get_pending_event_transfer(&env, event_id)
+    }
+
+    /// Returns the price currently charged for a tier, taking its price schedule into account.
+    /// A purchase is charged one unit price, so batches that would cross a sold-count step
+    /// are rejected with `PriceStepCrossed`.
     ///
     /// # Arguments
-    /// * `event_id` - The event whose inventory to increment.
-    /// * `tier_id` - The tier whose inventory to increment.
+    /// * `event_id` - The event the tier belongs to.
+    /// * `tier_id` - The tier to price.
+    pub fn get_current_price(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+    ) -> Result<i128, EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id).ok_or(EventRegistryError::EventNotFound)?;
+        let tier = event_info
+            .tiers
+            .get(tier_id)
+            .ok_or(EventRegistryError::TierNotFound)?;
+        Ok(current_price(&env, &tier))
+    }
+
+    /// Sets the resale policy of a ticket tier (owner or manager)
     ///
-    /// # Errors
-    /// * `UnauthorizedCaller` - If the invoker is not the registered TicketPayment contract.
-    /// * `EventNotFound` - If no event with the given ID exists.
-    /// * `EventInactive` - If the event is not currently active.
-    /// * `TierNotFound` - If the tier does not exist.
-    /// * `TierSupplyExceeded` - If the tier's limit has been reached.
-    /// * `MaxSupplyExceeded` - If the event's max supply has been reached (when max_supply > 0).
-    /// * `SupplyOverflow` - If incrementing would cause an i128 overflow.
-    pub fn increment_inventory(
+    /// # Arguments
+    /// * `caller` - The event owner or one of its managers.
+    /// * `event_id` - The event the tier belongs to.
+    /// * `tier_id` - The tier to update.
+    /// * `max_resale_bps` - Maximum resale price as a share of the tier price (0 disables resale).
+    /// * `royalty_bps` - Royalty paid to the organizer on each resale.
+    pub fn set_resale_policy(
         env: Env,
+        caller: Address,
         event_id: String,
         tier_id: String,
+        max_resale_bps: u32,
+        royalty_bps: u32,
     ) -> Result<(), EventRegistryError> {
-        let ticket_payment_addr =
-            storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)?;
-        ticket_payment_addr.require_auth();
-
         let mut event_info =
             storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        require_event_manager(&env, &event_info, &caller)?;
 
-        if !event_info.is_active {
-            return Err(EventRegistryError::EventInactive);
-        }
-
-        // Check global supply limits
-        if event_info.max_supply > 0 && event_info.current_supply >= event_info.max_supply {
-            return Err(EventRegistryError::MaxSupplyExceeded);
-        }
-
-        // Get and update tier
         let mut tier = event_info
             .tiers
             .get(tier_id.clone())
             .ok_or(EventRegistryError::TierNotFound)?;
+        tier.max_resale_bps = max_resale_bps;
+        tier.royalty_bps = royalty_bps;
+        validate_tier(&tier)?;
 
-        if tier.current_sold >= tier.tier_limit {
-            return Err(EventRegistryError::TierSupplyExceeded);
-        }
-
-        tier.current_sold = tier
-            .current_sold
-            .checked_add(1)
-            .ok_or(EventRegistryError::SupplyOverflow)?;
+        event_info.tiers.set(tier_id.clone(), tier);
+        storage::store_event(&env, event_info.clone());
 
-        event_info.tiers.set(tier_id, tier);
+        env.events().publish(
+            (AgoraEvent::ResalePolicyUpdated,),
+            ResalePolicyUpdatedEvent {
+                event_id,
+                tier_id,
+                max_resale_bps,
+                royalty_bps,
+                updated_by: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
-        event_info.current_supply = event_info
-            .current_supply
-            .checked_add(1)
-            .ok_or(EventRegistryError::SupplyOverflow)?;
+        Ok(())
+    }
 
-        storage::store_event(&env, event_info.clone());
+    /// Sets how many tickets a single buyer may purchase (owner or manager)
+    ///
+    /// # Arguments
+    /// * `caller` - The event owner or one of its managers.
+    /// * `event_id` - The event to update.
+    /// * `tier_id` - The tier to limit, or `None` for the limit across all tiers.
+    /// * `max_per_buyer` - Maximum tickets per buyer (0 removes the limit).
+    pub fn set_max_per_buyer(
+        env: Env,
+        caller: Address,
+        event_id: String,
+        tier_id: Option<String>,
+        max_per_buyer: u32,
+    ) -> Result<(), EventRegistryError> {
+        let mut event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        require_event_manager(&env, &event_info, &caller)?;
+
+        match tier_id.clone() {
+            Some(tier_id) => {
+                let mut tier = event_info
+                    .tiers
+                    .get(tier_id.clone())
+                    .ok_or(EventRegistryError::TierNotFound)?;
+                tier.max_per_buyer = max_per_buyer;
+                event_info.tiers.set(tier_id, tier);
+            }
+            None => event_info.max_per_buyer = max_per_buyer,
+        }
+        storage::store_event(&env, event_info);
 
         env.events().publish(
-            (AgoraEvent::InventoryIncremented,),
-            InventoryIncrementedEvent {
+            (AgoraEvent::PurchaseLimitUpdated,),
+            PurchaseLimitUpdatedEvent {
                 event_id,
-                new_supply: event_info.current_supply,
-                max_supply: event_info.max_supply,
+                tier_id,
+                max_per_buyer,
+                updated_by: caller,
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -376,64 +558,77 @@ impl EventRegistry {
         Ok(())
     }
 
-    /// Decrements the current_supply counter for a given event and tier.
-    /// This function is restricted to calls from the authorized TicketPayment contract upon refund.
+    /// Sets who may buy a ticket tier (owner or manager)
     ///
     /// # Arguments
-    /// * `event_id` - The event whose inventory to decrement.
-    /// * `tier_id` - The tier whose inventory to decrement.
-    ///
-    /// # Errors
-    /// * `UnauthorizedCaller` - If the invoker is not the registered TicketPayment contract.
-    /// * `EventNotFound` - If no event with the given ID exists.
-    /// * `TierNotFound` - If the tier does not exist.
-    /// * `SupplyUnderflow` - If decrementing would cause the supply to go below 0.
-    pub fn decrement_inventory(
+    /// * `caller` - The event owner or one of its managers.
+    /// * `event_id` - The event the tier belongs to.
+    /// * `tier_id` - The tier to gate.
+    /// * `access_rule` - `Open`, `Allowlist`, a Merkle root or a minimum token balance.
+    pub fn set_access_rule(
         env: Env,
+        caller: Address,
         event_id: String,
         tier_id: String,
+        access_rule: AccessRule,
     ) -> Result<(), EventRegistryError> {
-        let ticket_payment_addr =
-            storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)?;
-        ticket_payment_addr.require_auth();
-
         let mut event_info =
             storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        require_event_manager(&env, &event_info, &caller)?;
 
-        // Get and update tier
         let mut tier = event_info
             .tiers
             .get(tier_id.clone())
             .ok_or(EventRegistryError::TierNotFound)?;
+        tier.access_rule = access_rule.clone();
+        validate_tier(&tier)?;
 
-        if tier.current_sold <= 0 {
-            return Err(EventRegistryError::SupplyUnderflow);
-        }
+        event_info.tiers.set(tier_id.clone(), tier);
+        storage::store_event(&env, event_info);
 
-        tier.current_sold = tier
-            .current_sold
-            .checked_sub(1)
-            .ok_or(EventRegistryError::SupplyUnderflow)?;
+        env.events().publish(
+            (AgoraEvent::AccessRuleUpdated,),
+            AccessRuleUpdatedEvent {
+                event_id,
+                tier_id,
+                access_rule,
+                updated_by: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
-        event_info.tiers.set(tier_id, tier);
+        Ok(())
+    }
 
-        if event_info.current_supply <= 0 {
-            return Err(EventRegistryError::SupplyUnderflow);
+    /// Adds addresses to, or removes them from, a tier's allowlist (owner or manager).
+    /// The list only applies while the tier's access rule is `Allowlist`.
+    pub fn set_tier_allowlist(
+        env: Env,
+        caller: Address,
+        event_id: String,
+        tier_id: String,
+        accounts: Vec<Address>,
+        allowed: bool,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        require_event_manager(&env, &event_info, &caller)?;
+        if !event_info.tiers.contains_key(tier_id.clone()) {
+            return Err(EventRegistryError::TierNotFound);
         }
 
-        event_info.current_supply = event_info
-            .current_supply
-            .checked_sub(1)
-            .ok_or(EventRegistryError::SupplyUnderflow)?;
-
-        storage::store_event(&env, event_info.clone());
+        for account in accounts.iter() {
+            storage::set_allowlisted(&env, &event_id, &tier_id, &account, allowed);
+        }
 
         env.events().publish(
-            (crate::events::AgoraEvent::InventoryDecremented,),
-            crate::events::InventoryDecrementedEvent {
+            (AgoraEvent::AllowlistUpdated,),
+            AllowlistUpdatedEvent {
                 event_id,
-                new_supply: event_info.current_supply,
-                max_supply: event_info.max_supply,
+                tier_id,
+                count: accounts.len(),
+                allowed,
+                updated_by: caller,
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -441,33 +636,1588 @@ impl EventRegistry {
         Ok(())
     }
 
-    /// Upgrades the contract to a new WASM hash. Only callable by the administrator.
-    /// Performs post-upgrade state verification to ensure critical storage is intact.
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), EventRegistryError> {
-        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
-        admin.require_auth();
+    /// Returns whether an address is on a tier's allowlist.
+    pub fn is_allowlisted(env: Env, event_id: String, tier_id: String, account: Address) -> bool {
+        storage::is_allowlisted(&env, &event_id, &tier_id, &account)
+    }
 
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    /// Adds a new ticket tier to an event (owner or manager)
+    ///
+    /// The tier starts with no tickets sold, whatever `current_sold` is passed in.
+    ///
+    /// # Arguments
+    /// * `caller` - The event owner or one of its managers.
+    /// * `event_id` - The event to add the tier to.
+    /// * `tier_id` - Identifier of the new tier; must not be in use.
+    /// * `tier` - The tier definition.
+    pub fn add_tier(
+        env: Env,
+        caller: Address,
+        event_id: String,
+        tier_id: String,
+        tier: TicketTier,
+    ) -> Result<(), EventRegistryError> {
+        let mut event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        require_event_manager(&env, &event_info, &caller)?;
 
-        // Post-upgrade state verification
-        let verified_admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
-        storage::get_platform_wallet(&env).ok_or(EventRegistryError::NotInitialized)?;
+        if event_info.tiers.contains_key(tier_id.clone()) {
+            return Err(EventRegistryError::TierAlreadyExists);
+        }
+
+        let mut tier = tier;
+        tier.current_sold = 0;
+        tier.is_retired = false;
+        validate_tier(&tier)?;
+
+        event_info.tiers.set(tier_id.clone(), tier.clone());
+        validate_tier_limits(&event_info.tiers, event_info.max_supply)?;
+        validate_promo_codes(&env, &event_info)?;
+        storage::store_event(&env, event_info.clone());
 
         env.events().publish(
-            (AgoraEvent::ContractUpgraded,),
-            RegistryUpgradedEvent {
-                admin_address: verified_admin,
+            (AgoraEvent::TierAdded,),
+            TierAddedEvent {
+                event_id,
+                tier_id,
+                price: tier.price,
+                tier_limit: tier.tier_limit,
+                updated_by: caller,
                 timestamp: env.ledger().timestamp(),
             },
         );
 
         Ok(())
     }
-}
 
-fn validate_address(env: &Env, address: &Address) -> Result<(), EventRegistryError> {
-    if address == &env.current_contract_address() {
-        return Err(EventRegistryError::InvalidAddress);
+    /// Replaces the definition of an existing tier (owner or manager)
+    ///
+    /// Sales counts and the retired flag are kept from the stored tier, and the new
+    /// limit cannot drop below the tickets already sold.
+    ///
+    /// # Arguments
+    /// * `caller` - The event owner or one of its managers.
+    /// * `event_id` - The event the tier belongs to.
+    /// * `tier_id` - The tier to update.
+    /// * `tier` - The new tier definition.
+    pub fn update_tier(
+        env: Env,
+        caller: Address,
+        event_id: String,
+        tier_id: String,
+        tier: TicketTier,
+    ) -> Result<(), EventRegistryError> {
+        let mut event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        require_event_manager(&env, &event_info, &caller)?;
+
+        let existing = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+
+        let mut tier = tier;
+        tier.current_sold = existing.current_sold;
+        tier.is_retired = existing.is_retired;
+        if tier.tier_limit < tier.current_sold {
+            return Err(EventRegistryError::TierLimitBelowSold);
+        }
+        validate_tier(&tier)?;
+
+        event_info.tiers.set(tier_id.clone(), tier.clone());
+        validate_tier_limits(&event_info.tiers, event_info.max_supply)?;
+        validate_promo_codes(&env, &event_info)?;
+        storage::store_event(&env, event_info.clone());
+
+        env.events().publish(
+            (AgoraEvent::TierUpdated,),
+            TierUpdatedEvent {
+                event_id,
+                tier_id,
+                price: tier.price,
+                tier_limit: tier.tier_limit,
+                updated_by: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Retires a tier so it can no longer be bought (owner or manager)
+    ///
+    /// Tickets already sold stay valid and the tier keeps counting in supply totals.
+    ///
+    /// # Arguments
+    /// * `caller` - The event owner or one of its managers.
+    /// * `event_id` - The event the tier belongs to.
+    /// * `tier_id` - The tier to retire.
+    pub fn retire_tier(
+        env: Env,
+        caller: Address,
+        event_id: String,
+        tier_id: String,
+    ) -> Result<(), EventRegistryError> {
+        let mut event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        require_event_manager(&env, &event_info, &caller)?;
+
+        let mut tier = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+        if tier.is_retired {
+            return Err(EventRegistryError::TierRetired);
+        }
+        tier.is_retired = true;
+
+        event_info.tiers.set(tier_id.clone(), tier.clone());
+        storage::store_event(&env, event_info.clone());
+
+        env.events().publish(
+            (AgoraEvent::TierRetired,),
+            TierRetiredEvent {
+                event_id,
+                tier_id,
+                current_sold: tier.current_sold,
+                updated_by: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Imports events migrated from an older deployment (admin only)
+    ///
+    /// Every record is validated like `register_event`, including its sales counters.
+    /// Existing events are never overwritten and one invalid record rejects the whole
+    /// batch. Emits an `EventImported` audit event per record.
+    ///
+    /// # Arguments
+    /// * `events` - The event records to import.
+    pub fn import_events(env: Env, events: Vec<EventInfo>) -> Result<(), EventRegistryError> {
+        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        admin.require_auth();
+
+        for event_info in events.iter() {
+            validate_imported_event(&env, &event_info)?;
+            storage::store_event(&env, event_info.clone());
+
+            env.events().publish(
+                (AgoraEvent::EventImported,),
+                EventImportedEvent {
+                    event_id: event_info.event_id,
+                    organizer_address: event_info.organizer_address,
+                    payment_address: event_info.payment_address,
+                    platform_fee_percent: event_info.platform_fee_percent,
+                    current_supply: event_info.current_supply,
+                    imported_by: admin.clone(),
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves an event by its ID.
+    pub fn get_event(env: Env, event_id: String) -> Option<EventInfo> {
+        storage::get_event(&env, event_id)
+    }
+
+    /// Checks if an event exists.
+    pub fn event_exists(env: Env, event_id: String) -> bool {
+        storage::event_exists(&env, event_id)
+    }
+
+    /// Retrieves all event IDs for an organizer.
+    pub fn get_organizer_events(env: Env, organizer: Address) -> Vec<String> {
+        storage::get_organizer_events(&env, &organizer)
+    }
+
+    /// Updates the platform fee percentage. Only callable by the administrator.
+    pub fn set_platform_fee(env: Env, new_fee_percent: u32) -> Result<(), EventRegistryError> {
+        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        admin.require_auth();
+
+        if new_fee_percent > 10000 {
+            return Err(EventRegistryError::InvalidFeePercent);
+        }
+
+        storage::set_platform_fee(&env, new_fee_percent);
+
+        // Emit fee update event using contract event type
+        env.events().publish(
+            (AgoraEvent::FeeUpdated,),
+            FeeUpdatedEvent { new_fee_percent },
+        );
+
+        Ok(())
+    }
+
+    /// Sets a platform fee override for an organizer or a single event. Only callable
+    /// by the administrator. Event overrides take precedence over organizer overrides,
+    /// which take precedence over the global fee.
+    ///
+    /// # Arguments
+    /// * `target` - The organizer or event the override applies to.
+    /// * `fee_percent` - Fee in basis points (0 for fee-free plans).
+    /// * `expires_at` - Timestamp after which the override lapses, or `None` for no expiry.
+    pub fn set_fee_override(
+        env: Env,
+        target: FeeOverrideTarget,
+        fee_percent: u32,
+        expires_at: Option<u64>,
+    ) -> Result<(), EventRegistryError> {
+        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        admin.require_auth();
+
+        if fee_percent > 10000 {
+            return Err(EventRegistryError::InvalidFeePercent);
+        }
+        if let Some(expiry) = expires_at {
+            if expiry <= env.ledger().timestamp() {
+                return Err(EventRegistryError::InvalidEventTime);
+            }
+        }
+
+        storage::set_fee_override(
+            &env,
+            &FeeOverride {
+                target: target.clone(),
+                fee_percent,
+                expires_at,
+            },
+        );
+
+        env.events().publish(
+            (AgoraEvent::FeeOverrideSet,),
+            FeeOverrideSetEvent {
+                target,
+                fee_percent,
+                expires_at,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Removes a platform fee override. Only callable by the administrator.
+    pub fn remove_fee_override(
+        env: Env,
+        target: FeeOverrideTarget,
+    ) -> Result<(), EventRegistryError> {
+        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        admin.require_auth();
+
+        if storage::get_fee_override(&env, &target).is_none() {
+            return Err(EventRegistryError::FeeOverrideNotFound);
+        }
+        storage::remove_fee_override(&env, &target);
+
+        env.events().publish(
+            (AgoraEvent::FeeOverrideRemoved,),
+            FeeOverrideRemovedEvent {
+                target,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns every fee override that has not expired.
+    pub fn get_active_fee_overrides(env: Env) -> Vec<FeeOverride> {
+        let mut overrides = Vec::new(&env);
+        for target in storage::get_fee_override_targets(&env).iter() {
+            if let Some(fee_override) = active_fee_override(&env, &target) {
+                overrides.push_back(fee_override);
+            }
+        }
+        overrides
+    }
+
+    /// Returns the current platform fee percentage.
+    pub fn get_platform_fee(env: Env) -> u32 {
+        storage::get_platform_fee(&env)
+    }
+
+    /// Proposes a new administrator. Only callable by the current administrator.
+    /// The change takes effect once the proposed address calls `accept_admin`;
+    /// a new proposal replaces any pending one.
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), EventRegistryError> {
+        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        admin.require_auth();
+
+        validate_address(&env, &new_admin)?;
+        storage::set_pending_admin(&env, &new_admin);
+
+        env.events().publish(
+            (AgoraEvent::AdminProposed,),
+            AdminProposedEvent {
+                current_admin: admin,
+                proposed_admin: new_admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Completes an administrator change. Only callable by the proposed administrator.
+    pub fn accept_admin(env: Env) -> Result<(), EventRegistryError> {
+        let previous_admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        let new_admin =
+            storage::get_pending_admin(&env).ok_or(EventRegistryError::NoPendingAdmin)?;
+        new_admin.require_auth();
+
+        storage::set_admin(&env, &new_admin);
+        storage::remove_pending_admin(&env);
+
+        env.events().publish(
+            (AgoraEvent::AdminChanged,),
+            AdminChangedEvent {
+                previous_admin,
+                new_admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the administrator waiting to accept the role, if any.
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        storage::get_pending_admin(&env)
+    }
+
+    /// Updates the platform wallet address. Only callable by the administrator.
+    pub fn set_platform_wallet(env: Env, new_wallet: Address) -> Result<(), EventRegistryError> {
+        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        admin.require_auth();
+
+        validate_address(&env, &new_wallet)?;
+        let old_wallet =
+            storage::get_platform_wallet(&env).ok_or(EventRegistryError::NotInitialized)?;
+        storage::set_platform_wallet(&env, &new_wallet);
+
+        env.events().publish(
+            (AgoraEvent::PlatformWalletUpdated,),
+            PlatformWalletUpdatedEvent {
+                old_wallet,
+                new_wallet,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Pauses event registration and ticket sales in an emergency. Inventory can still
+    /// be released for refunds and queries keep working. Only callable by the administrator.
+    ///
+    /// # Arguments
+    /// * `reason` - Reason code recorded in the emitted event.
+    pub fn pause(env: Env, reason: u32) -> Result<(), EventRegistryError> {
+        set_pause_state(&env, true, reason)
+    }
+
+    /// Lifts an emergency pause. Only callable by the administrator.
+    ///
+    /// # Arguments
+    /// * `reason` - Reason code recorded in the emitted event.
+    pub fn unpause(env: Env, reason: u32) -> Result<(), EventRegistryError> {
+        set_pause_state(&env, false, reason)
+    }
+
+    /// Returns whether the contract is paused.
+    pub fn is_paused(env: Env) -> bool {
+        storage::is_paused(&env)
+    }
+
+    /// Returns the current administrator address.
+    pub fn get_admin(env: Env) -> Result<Address, EventRegistryError> {
+        storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)
+    }
+
+    /// Returns the current platform wallet address.
+    pub fn get_platform_wallet(env: Env) -> Result<Address, EventRegistryError> {
+        storage::get_platform_wallet(&env).ok_or(EventRegistryError::NotInitialized)
+    }
+
+    /// Sets the authorized TicketPayment contract address. Only callable by the administrator.
+    ///
+    /// # Arguments
+    /// * `ticket_payment_address` - The address of the TicketPayment contract authorized
+    ///   to call `increment_inventory`.
+    pub fn set_ticket_payment_contract(
+        env: Env,
+        ticket_payment_address: Address,
+    ) -> Result<(), EventRegistryError> {
+        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        admin.require_auth();
+
+        validate_address(&env, &ticket_payment_address)?;
+
+        storage::set_ticket_payment_contract(&env, &ticket_payment_address);
+        Ok(())
+    }
+
+    /// Returns the authorized TicketPayment contract address.
+    pub fn get_ticket_payment_contract(env: Env) -> Result<Address, EventRegistryError> {
+        storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)
+    }
+
+    /// Increments the current_supply counter for a given event and tier by one.
+    /// This function is restricted to calls from the authorized TicketPayment contract.
+    /// No reservation is consumed; see `increment_inventory_bulk` for the errors returned.
+    pub fn increment_inventory(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+    ) -> Result<(), EventRegistryError> {
+        let ticket_payment_addr =
+            storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)?;
+        ticket_payment_addr.require_auth();
+
+        record_sale(&env, event_id, tier_id, None, 1)
+    }
+
+    /// Increments the current_supply counter for a given event and tier by `quantity`.
+    /// The tier limit and the event max supply are checked for the whole batch, so
+    /// either every ticket in the batch is counted or none is. Units held for other
+    /// buyers count against both limits; the buyer's own hold on the tier is consumed.
+    /// This function is restricted to calls from the authorized TicketPayment contract.
+    ///
+    /// # Arguments
+    /// * `event_id` - The event whose inventory to increment.
+    /// * `tier_id` - The tier whose inventory to increment.
+    /// * `buyer` - The buyer paying for the batch.
+    /// * `quantity` - The number of tickets sold in the batch.
+    ///
+    /// # Errors
+    /// * `UnauthorizedCaller` - If the invoker is not the registered TicketPayment contract.
+    /// * `InvalidQuantity` - If `quantity` is zero.
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `EventInactive` - If the event is not currently active.
+    /// * `TierNotFound` - If the tier does not exist.
+    /// * `TierSupplyExceeded` - If the batch would exceed the tier's limit.
+    /// * `MaxSupplyExceeded` - If the batch would exceed the event's max supply (when max_supply > 0).
+    /// * `SupplyOverflow` - If incrementing would cause an i128 overflow.
+    pub fn increment_inventory_bulk(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        buyer: Address,
+        quantity: u32,
+    ) -> Result<(), EventRegistryError> {
+        let ticket_payment_addr =
+            storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)?;
+        ticket_payment_addr.require_auth();
+
+        record_sale(&env, event_id, tier_id, Some(buyer), quantity)
+    }
+
+    /// Holds `quantity` units of a tier for a buyer until `expires_at`, so they cannot
+    /// sell out between checkout and payment. Held units count against the tier limit
+    /// and the event max supply. A new hold replaces the buyer's previous hold on the
+    /// tier. Expired holds are released, first to the tier's waitlist, the next time the
+    /// event's inventory changes.
+    ///
+    /// # Arguments
+    /// * `caller` - The TicketPayment contract or the platform administrator (checkout backend).
+    /// * `event_id` - The event to reserve in.
+    /// * `tier_id` - The tier to reserve in.
+    /// * `buyer` - The buyer the units are held for.
+    /// * `quantity` - The number of units to hold.
+    /// * `expires_at` - Timestamp at which the hold lapses.
+    ///
+    /// # Errors
+    /// * `UnauthorizedCaller` - If `caller` is neither the TicketPayment contract nor the admin.
+    /// * `InvalidQuantity` - If `quantity` is zero.
+    /// * `InvalidEventTime` - If `expires_at` is not in the future.
+    /// * `TierSupplyExceeded` / `MaxSupplyExceeded` - If not enough units are available.
+    pub fn reserve_inventory(
+        env: Env,
+        caller: Address,
+        event_id: String,
+        tier_id: String,
+        buyer: Address,
+        quantity: u32,
+        expires_at: u64,
+    ) -> Result<(), EventRegistryError> {
+        caller.require_auth();
+        ensure_not_paused(&env)?;
+        if Some(caller.clone()) != storage::get_ticket_payment_contract(&env)
+            && Some(caller) != storage::get_admin(&env)
+        {
+            return Err(EventRegistryError::UnauthorizedCaller);
+        }
+
+        if quantity == 0 {
+            return Err(EventRegistryError::InvalidQuantity);
+        }
+        if expires_at <= env.ledger().timestamp() {
+            return Err(EventRegistryError::InvalidEventTime);
+        }
+
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        if event_info.status != EventStatus::Published {
+            return Err(EventRegistryError::EventInactive);
+        }
+        let tier = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+        if tier.is_retired {
+            return Err(EventRegistryError::TierRetired);
+        }
+        check_sales_window(&env, &event_info, &tier)?;
+
+        release_expired_holds(&env, &event_info);
+        let mut reservations = active_reservations(&env, &event_id);
+        if let Some(index) = find_reservation(&reservations, &tier_id, &buyer) {
+            reservations.remove(index);
+        }
+        reservations.push_back(Reservation {
+            tier_id: tier_id.clone(),
+            buyer: buyer.clone(),
+            quantity,
+            expires_at,
+        });
+        check_availability(&event_info, &tier_id, &tier, &reservations, 0)?;
+        storage::set_reservations(&env, &event_id, &reservations);
+
+        env.events().publish(
+            (AgoraEvent::InventoryReserved,),
+            InventoryReservedEvent {
+                event_id,
+                tier_id,
+                buyer,
+                quantity,
+                expires_at,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Releases a buyer's hold on a tier before it expires.
+    ///
+    /// # Arguments
+    /// * `caller` - The buyer, the TicketPayment contract or the platform administrator.
+    ///
+    /// # Errors
+    /// * `UnauthorizedCaller` - If `caller` may not release the hold.
+    /// * `ReservationNotFound` - If the buyer has no active hold on the tier.
+    pub fn release_reservation(
+        env: Env,
+        caller: Address,
+        event_id: String,
+        tier_id: String,
+        buyer: Address,
+    ) -> Result<(), EventRegistryError> {
+        caller.require_auth();
+        if caller != buyer
+            && Some(caller.clone()) != storage::get_ticket_payment_contract(&env)
+            && Some(caller.clone()) != storage::get_admin(&env)
+        {
+            return Err(EventRegistryError::UnauthorizedCaller);
+        }
+
+        let mut reservations = active_reservations(&env, &event_id);
+        let index = find_reservation(&reservations, &tier_id, &buyer)
+            .ok_or(EventRegistryError::ReservationNotFound)?;
+        let reservation = reservations.get(index).unwrap();
+        reservations.remove(index);
+        storage::set_reservations(&env, &event_id, &reservations);
+
+        env.events().publish(
+            (AgoraEvent::ReservationReleased,),
+            ReservationReleasedEvent {
+                event_id,
+                tier_id,
+                buyer,
+                quantity: reservation.quantity,
+                released_by: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns a buyer's active hold on a tier, if any.
+    pub fn get_reservation(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        buyer: Address,
+    ) -> Option<Reservation> {
+        let reservations = active_reservations(&env, &event_id);
+        find_reservation(&reservations, &tier_id, &buyer).and_then(|i| reservations.get(i))
+    }
+
+    /// Returns the sold, held and available units of a tier. Available units account
+    /// for both the tier limit and the event max supply.
+    pub fn get_tier_availability(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+    ) -> Result<TierAvailability, EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        let tier = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+        let reservations = active_reservations(&env, &event_id);
+
+        let held = held_units(&reservations, Some(&tier_id));
+        let available = available_units(&event_info, &tier_id, &tier, &reservations);
+
+        Ok(TierAvailability {
+            tier_limit: tier.tier_limit,
+            sold: tier.current_sold,
+            held,
+            available,
+        })
+    }
+
+    /// Adds a promo code to an event (owner or manager). Only the SHA-256 hash of
+    /// the code is stored, so codes cannot be read from the ledger.
+    ///
+    /// # Arguments
+    /// * `caller` - The event owner or one of its managers.
+    /// * `event_id` - The event the code is valid for.
+    /// * `code_hash` - SHA-256 hash of the code text.
+    /// * `discount` - Percentage or fixed amount taken off each ticket.
+    /// * `tiers` - Tiers the code applies to (empty = every tier).
+    /// * `max_redemptions` - Maximum number of tickets the code can discount (0 = unlimited).
+    /// * `expires_at` - Timestamp from which the code is rejected (0 = never expires).
+    ///
+    /// # Errors
+    /// * `InvalidDiscount` - If the discount is zero, at least 100%, or not below every price
+    ///   a covered tier can reach.
+    /// * `TierNotFound` - If a listed tier does not exist.
+    /// * `PromoCodeAlreadyExists` - If the code is already set on the event.
+    pub fn add_promo_code(
+        env: Env,
+        caller: Address,
+        event_id: String,
+        code_hash: BytesN<32>,
+        discount: Discount,
+        tiers: Vec<String>,
+        max_redemptions: u32,
+        expires_at: u64,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        require_event_manager(&env, &event_info, &caller)?;
+
+        if storage::get_promo_code(&env, &event_id, &code_hash).is_some() {
+            return Err(EventRegistryError::PromoCodeAlreadyExists);
+        }
+        for tier_id in tiers.iter() {
+            if !event_info.tiers.contains_key(tier_id) {
+                return Err(EventRegistryError::TierNotFound);
+            }
+        }
+        match discount {
+            Discount::Percentage(bps) if bps == 0 || bps >= 10000 => {
+                return Err(EventRegistryError::InvalidDiscount);
+            }
+            Discount::Fixed(amount) if amount <= 0 => {
+                return Err(EventRegistryError::InvalidDiscount);
+            }
+            Discount::Fixed(amount) => validate_fixed_discount(&event_info.tiers, &tiers, amount)?,
+            _ => {}
+        }
+
+        storage::set_promo_code(
+            &env,
+            &event_id,
+            &PromoCode {
+                code_hash: code_hash.clone(),
+                discount,
+                tiers,
+                max_redemptions,
+                redemptions: 0,
+                expires_at,
+            },
+        );
+
+        env.events().publish(
+            (AgoraEvent::PromoCodeAdded,),
+            PromoCodeUpdatedEvent {
+                event_id,
+                code_hash,
+                updated_by: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Removes a promo code from an event (owner or manager).
+    pub fn remove_promo_code(
+        env: Env,
+        caller: Address,
+        event_id: String,
+        code_hash: BytesN<32>,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        require_event_manager(&env, &event_info, &caller)?;
+
+        if storage::get_promo_code(&env, &event_id, &code_hash).is_none() {
+            return Err(EventRegistryError::PromoCodeNotFound);
+        }
+        storage::remove_promo_code(&env, &event_id, &code_hash);
+
+        env.events().publish(
+            (AgoraEvent::PromoCodeRemoved,),
+            PromoCodeUpdatedEvent {
+                event_id,
+                code_hash,
+                updated_by: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns a promo code by its hash.
+    pub fn get_promo_code(env: Env, event_id: String, code_hash: BytesN<32>) -> Option<PromoCode> {
+        storage::get_promo_code(&env, &event_id, &code_hash)
+    }
+
+    /// Checks a promo code for a tier and counts one redemption per ticket. Returns the
+    /// code so the caller can apply its discount. Restricted to the TicketPayment contract.
+    ///
+    /// # Arguments
+    /// * `code` - The code text; it is hashed to look the code up.
+    /// * `quantity` - The number of tickets the code discounts.
+    ///
+    /// # Errors
+    /// * `PromoCodeNotFound` - If no code with that text exists on the event.
+    /// * `PromoCodeExpired` - If the code has expired.
+    /// * `PromoCodeNotApplicable` - If the code does not cover the tier.
+    /// * `PromoCodeExhausted` - If the tickets would exceed the code's redemption cap.
+    pub fn redeem_promo_code(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        code: Bytes,
+        quantity: u32,
+    ) -> Result<PromoCode, EventRegistryError> {
+        let ticket_payment_addr =
+            storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)?;
+        ticket_payment_addr.require_auth();
+
+        let code_hash: BytesN<32> = env.crypto().sha256(&code).into();
+        let mut promo_code = storage::get_promo_code(&env, &event_id, &code_hash)
+            .ok_or(EventRegistryError::PromoCodeNotFound)?;
+
+        if promo_code.expires_at != 0 && env.ledger().timestamp() >= promo_code.expires_at {
+            return Err(EventRegistryError::PromoCodeExpired);
+        }
+        if !promo_code.tiers.is_empty() && !promo_code.tiers.contains(&tier_id) {
+            return Err(EventRegistryError::PromoCodeNotApplicable);
+        }
+        let redemptions = promo_code
+            .redemptions
+            .checked_add(quantity)
+            .ok_or(EventRegistryError::PromoCodeExhausted)?;
+        if promo_code.max_redemptions != 0 && redemptions > promo_code.max_redemptions {
+            return Err(EventRegistryError::PromoCodeExhausted);
+        }
+
+        promo_code.redemptions = redemptions;
+        storage::set_promo_code(&env, &event_id, &promo_code);
+
+        env.events().publish(
+            (AgoraEvent::PromoCodeRedeemed,),
+            PromoCodeRedeemedEvent {
+                event_id,
+                tier_id,
+                code_hash,
+                quantity,
+                redemptions: promo_code.redemptions,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(promo_code)
+    }
+
+    /// Gives back the redemptions of refunded tickets. Restricted to the TicketPayment
+    /// contract; codes removed in the meantime are ignored.
+    pub fn credit_promo_code(
+        env: Env,
+        event_id: String,
+        code_hash: BytesN<32>,
+        quantity: u32,
+    ) -> Result<(), EventRegistryError> {
+        let ticket_payment_addr =
+            storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)?;
+        ticket_payment_addr.require_auth();
+
+        if let Some(mut promo_code) = storage::get_promo_code(&env, &event_id, &code_hash) {
+            promo_code.redemptions = promo_code.redemptions.saturating_sub(quantity);
+            storage::set_promo_code(&env, &event_id, &promo_code);
+        }
+        Ok(())
+    }
+
+    /// Adds a buyer to the waitlist of a sold-out tier. When a refund frees enough
+    /// capacity, the first buyer in line is offered a reservation for their quantity
+    /// that lasts `WAITLIST_OFFER_DURATION` seconds.
+    ///
+    /// # Errors
+    /// * `InvalidQuantity` - If `quantity` is zero or above the tier limit or a per-buyer limit.
+    /// * `TicketsAvailable` - If the tier can fill the order right now.
+    /// * `AlreadyOnWaitlist` - If the buyer is already waiting for this tier.
+    pub fn join_waitlist(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        buyer: Address,
+        quantity: u32,
+    ) -> Result<(), EventRegistryError> {
+        buyer.require_auth();
+        ensure_not_paused(&env)?;
+
+        if quantity == 0 {
+            return Err(EventRegistryError::InvalidQuantity);
+        }
+
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        if event_info.status != EventStatus::Published {
+            return Err(EventRegistryError::EventInactive);
+        }
+        let tier = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+        if tier.is_retired {
+            return Err(EventRegistryError::TierRetired);
+        }
+        check_sales_window(&env, &event_info, &tier)?;
+
+        // An entry that can never be filled would block everyone behind it
+        let exceeds = |limit: u32| limit > 0 && quantity > limit;
+        if quantity as i128 > tier.tier_limit
+            || exceeds(tier.max_per_buyer)
+            || exceeds(event_info.max_per_buyer)
+        {
+            return Err(EventRegistryError::InvalidQuantity);
+        }
+
+        release_expired_holds(&env, &event_info);
+        let reservations = active_reservations(&env, &event_id);
+        if available_units(&event_info, &tier_id, &tier, &reservations) >= quantity as i128 {
+            return Err(EventRegistryError::TicketsAvailable);
+        }
+
+        let mut waitlist = storage::get_waitlist(&env, &event_id, &tier_id);
+        if waitlist.iter().any(|entry| entry.buyer == buyer) {
+            return Err(EventRegistryError::AlreadyOnWaitlist);
+        }
+        waitlist.push_back(WaitlistEntry {
+            buyer: buyer.clone(),
+            quantity,
+            joined_at: env.ledger().timestamp(),
+        });
+        storage::set_waitlist(&env, &event_id, &tier_id, &waitlist);
+
+        env.events().publish(
+            (AgoraEvent::WaitlistJoined,),
+            WaitlistJoinedEvent {
+                event_id,
+                tier_id,
+                buyer,
+                quantity,
+                position: waitlist.len(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Removes a buyer from a tier's waitlist.
+    ///
+    /// # Errors
+    /// * `NotOnWaitlist` - If the buyer is not waiting for this tier.
+    pub fn leave_waitlist(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        buyer: Address,
+    ) -> Result<(), EventRegistryError> {
+        buyer.require_auth();
+
+        let mut waitlist = storage::get_waitlist(&env, &event_id, &tier_id);
+        let index = waitlist
+            .iter()
+            .position(|entry| entry.buyer == buyer)
+            .ok_or(EventRegistryError::NotOnWaitlist)?;
+        waitlist.remove(index as u32);
+        storage::set_waitlist(&env, &event_id, &tier_id, &waitlist);
+
+        Ok(())
+    }
+
+    /// Returns the buyers waiting for a tier, first in line first.
+    pub fn get_waitlist(env: Env, event_id: String, tier_id: String) -> Vec<WaitlistEntry> {
+        storage::get_waitlist(&env, &event_id, &tier_id)
+    }
+
+    /// Decrements the current_supply counter for a given event and tier by one.
+    /// This function is restricted to calls from the authorized TicketPayment contract upon refund.
+    ///
+    /// See `decrement_inventory_bulk` for the errors returned.
+    pub fn decrement_inventory(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+    ) -> Result<(), EventRegistryError> {
+        Self::decrement_inventory_bulk(env, event_id, tier_id, 1)
+    }
+
+    /// Decrements the current_supply counter for a given event and tier by `quantity`.
+    /// Freed capacity is offered to the tier's waitlist, first in line first.
+    /// This function is restricted to calls from the authorized TicketPayment contract upon refund.
+    ///
+    /// # Arguments
+    /// * `event_id` - The event whose inventory to decrement.
+    /// * `tier_id` - The tier whose inventory to decrement.
+    /// * `quantity` - The number of tickets returned to inventory.
+    ///
+    /// # Errors
+    /// * `UnauthorizedCaller` - If the invoker is not the registered TicketPayment contract.
+    /// * `InvalidQuantity` - If `quantity` is zero.
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `TierNotFound` - If the tier does not exist.
+    /// * `SupplyUnderflow` - If decrementing would cause the supply to go below 0.
+    pub fn decrement_inventory_bulk(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        quantity: u32,
+    ) -> Result<(), EventRegistryError> {
+        let ticket_payment_addr =
+            storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)?;
+        ticket_payment_addr.require_auth();
+
+        if quantity == 0 {
+            return Err(EventRegistryError::InvalidQuantity);
+        }
+        let quantity_i128 = quantity as i128;
+
+        let mut event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+
+        // Get and update tier
+        let mut tier = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+
+        if tier.current_sold < quantity_i128 {
+            return Err(EventRegistryError::SupplyUnderflow);
+        }
+
+        tier.current_sold = tier
+            .current_sold
+            .checked_sub(quantity_i128)
+            .ok_or(EventRegistryError::SupplyUnderflow)?;
+
+        event_info.tiers.set(tier_id.clone(), tier);
+
+        if event_info.current_supply < quantity_i128 {
+            return Err(EventRegistryError::SupplyUnderflow);
+        }
+
+        event_info.current_supply = event_info
+            .current_supply
+            .checked_sub(quantity_i128)
+            .ok_or(EventRegistryError::SupplyUnderflow)?;
+
+        storage::store_event(&env, event_info.clone());
+        offer_to_waitlist(&env, &event_info, &tier_id);
+
+        env.events().publish(
+            (crate::events::AgoraEvent::InventoryDecremented,),
+            crate::events::InventoryDecrementedEvent {
+                event_id,
+                quantity,
+                new_supply: event_info.current_supply,
+                max_supply: event_info.max_supply,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Upgrades the contract to a new WASM hash. Only callable by the administrator.
+    /// Performs post-upgrade state verification to ensure critical storage is intact.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), EventRegistryError> {
+        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        admin.require_auth();
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        // Post-upgrade state verification
+        let verified_admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        storage::get_platform_wallet(&env).ok_or(EventRegistryError::NotInitialized)?;
+
+        env.events().publish(
+            (AgoraEvent::ContractUpgraded,),
+            RegistryUpgradedEvent {
+                admin_address: verified_admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns the override of `target` if it exists and has not expired.
+fn active_fee_override(env: &Env, target: &FeeOverrideTarget) -> Option<FeeOverride> {
+    let fee_override = storage::get_fee_override(env, target)?;
+    match fee_override.expires_at {
+        Some(expiry) if env.ledger().timestamp() >= expiry => None,
+        _ => Some(fee_override),
+    }
+}
+
+/// Resolves the platform fee for an event: event override, then organizer override,
+/// then `default_fee`.
+fn effective_platform_fee(
+    env: &Env,
+    event_id: &String,
+    organizer: &Address,
+    default_fee: u32,
+) -> u32 {
+    active_fee_override(env, &FeeOverrideTarget::Event(event_id.clone()))
+        .or_else(|| active_fee_override(env, &FeeOverrideTarget::Organizer(organizer.clone())))
+        .map(|fee_override| fee_override.fee_percent)
+        .unwrap_or(default_fee)
+}
+
+#[allow(deprecated)]
+fn set_pause_state(env: &Env, paused: bool, reason: u32) -> Result<(), EventRegistryError> {
+    let admin = storage::get_admin(env).ok_or(EventRegistryError::NotInitialized)?;
+    admin.require_auth();
+
+    storage::set_paused(env, paused);
+
+    let topic = if paused {
+        AgoraEvent::ContractPaused
+    } else {
+        AgoraEvent::ContractUnpaused
+    };
+    env.events().publish(
+        (topic,),
+        PauseStatusEvent {
+            reason,
+            updated_by: admin,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+fn ensure_not_paused(env: &Env) -> Result<(), EventRegistryError> {
+    if storage::is_paused(env) {
+        return Err(EventRegistryError::ContractPaused);
+    }
+    Ok(())
+}
+
+/// Authorizes `caller` as the event's owner or one of its managers.
+fn require_event_manager(
+    env: &Env,
+    event_info: &EventInfo,
+    caller: &Address,
+) -> Result<(), EventRegistryError> {
+    caller.require_auth();
+    if *caller == event_info.organizer_address {
+        return Ok(());
+    }
+    match storage::get_event_role(env, event_info.event_id.clone(), caller) {
+        Some(EventRole::Manager) => Ok(()),
+        _ => Err(EventRegistryError::Unauthorized),
+    }
+}
+
+fn is_valid_transition(from: EventStatus, to: EventStatus) -> bool {
+    matches!(
+        (from, to),
+        (EventStatus::Draft, EventStatus::Published)
+            | (EventStatus::Draft, EventStatus::Cancelled)
+            | (EventStatus::Published, EventStatus::SalesPaused)
+            | (EventStatus::Published, EventStatus::Cancelled)
+            | (EventStatus::Published, EventStatus::Completed)
+            | (EventStatus::SalesPaused, EventStatus::Published)
+            | (EventStatus::SalesPaused, EventStatus::Cancelled)
+            | (EventStatus::SalesPaused, EventStatus::Completed)
+    )
+}
+
+fn validate_address(env: &Env, address: &Address) -> Result<(), EventRegistryError> {
+    if address == &env.current_contract_address() {
+        return Err(EventRegistryError::InvalidAddress);
+    }
+    Ok(())
+}
+
+/// Applies `register_event`'s checks to a migrated record, plus consistency of its
+/// fee and sales counters.
+fn validate_imported_event(env: &Env, event_info: &EventInfo) -> Result<(), EventRegistryError> {
+    validate_address(env, &event_info.organizer_address)?;
+    validate_address(env, &event_info.payment_address)?;
+    validate_metadata_cid(env, &event_info.metadata_cid)?;
+
+    if storage::event_exists(env, event_info.event_id.clone()) {
+        return Err(EventRegistryError::EventAlreadyExists);
+    }
+    if event_info.platform_fee_percent > 10000 {
+        return Err(EventRegistryError::InvalidFeePercent);
+    }
+    if event_info.end_time <= env.ledger().timestamp()
+        || event_info.start_time >= event_info.end_time
+    {
+        return Err(EventRegistryError::InvalidEventTime);
+    }
+
+    let mut total_sold: i128 = 0;
+    for tier in event_info.tiers.values() {
+        validate_tier(&tier)?;
+        if tier.current_sold < 0 || tier.current_sold > tier.tier_limit {
+            return Err(EventRegistryError::TierSupplyExceeded);
+        }
+        total_sold = total_sold
+            .checked_add(tier.current_sold)
+            .ok_or(EventRegistryError::SupplyOverflow)?;
+    }
+    validate_tier_limits(&event_info.tiers, event_info.max_supply)?;
+
+    if event_info.current_supply != total_sold {
+        return Err(EventRegistryError::SupplyMismatch);
+    }
+    if event_info.max_supply > 0 && event_info.current_supply > event_info.max_supply {
+        return Err(EventRegistryError::MaxSupplyExceeded);
+    }
+    Ok(())
+}
+
+fn validate_tier(tier: &TicketTier) -> Result<(), EventRegistryError> {
+    if tier.royalty_bps > 10000 {
+        return Err(EventRegistryError::InvalidResalePolicy);
+    }
+
+    if tier.sale_end != 0 && tier.sale_end <= tier.sale_start {
+        return Err(EventRegistryError::InvalidSalesWindow);
+    }
+
+    if let AccessRule::TokenHolder(_, min_balance) = tier.access_rule {
+        if min_balance <= 0 {
+            return Err(EventRegistryError::InvalidAccessRule);
+        }
+    }
+
+    let mut previous_time: Option<u64> = None;
+    let mut previous_sold: Option<i128> = None;
+    for step in tier.price_schedule.iter() {
+        if step.price <= 0 {
+            return Err(EventRegistryError::InvalidPriceSchedule);
+        }
+        // Steps of each kind must be strictly increasing
+        let increasing = match step.trigger {
+            PriceTrigger::Time(at) => {
+                let ok = previous_time.is_none_or(|previous| at > previous);
+                previous_time = Some(at);
+                ok
+            }
+            PriceTrigger::Sold(count) => {
+                let ok = count > 0 && previous_sold.is_none_or(|previous| count > previous);
+                previous_sold = Some(count);
+                ok
+            }
+        };
+        if !increasing {
+            return Err(EventRegistryError::InvalidPriceSchedule);
+        }
+    }
+
+    let mut previous_deadline: Option<u64> = None;
+    for window in tier.refund_schedule.iter() {
+        if window.refund_bps > 10000 {
+            return Err(EventRegistryError::InvalidRefundSchedule);
+        }
+        if let Some(previous) = previous_deadline {
+            if window.deadline <= previous {
+                return Err(EventRegistryError::InvalidRefundSchedule);
+            }
+        }
+        previous_deadline = Some(window.deadline);
+    }
+    Ok(())
+}
+
+/// Returns the tier price at the current ledger time and sales count.
+fn current_price(env: &Env, tier: &TicketTier) -> i128 {
+    let now = env.ledger().timestamp();
+    let mut price = tier.price;
+    for step in tier.price_schedule.iter() {
+        let reached = match step.trigger {
+            PriceTrigger::Time(at) => now >= at,
+            PriceTrigger::Sold(count) => tier.current_sold >= count,
+        };
+        if reached {
+            price = step.price;
+        }
+    }
+    price
+}
+
+/// Rejects a sale taking the tier from its current count to `new_sold` if it crosses
+/// a sold-count price step, since the whole batch is charged a single unit price.
+fn check_price_steps(tier: &TicketTier, new_sold: i128) -> Result<(), EventRegistryError> {
+    for step in tier.price_schedule.iter() {
+        if let PriceTrigger::Sold(count) = step.trigger {
+            if tier.current_sold < count && count < new_sold {
+                return Err(EventRegistryError::PriceStepCrossed);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Counts a sale of `quantity` units against the tier and event limits, consuming
+/// the buyer's hold on the tier first. Holds of other buyers stay reserved.
+#[allow(deprecated)]
+fn record_sale(
+    env: &Env,
+    event_id: String,
+    tier_id: String,
+    buyer: Option<Address>,
+    quantity: u32,
+) -> Result<(), EventRegistryError> {
+    ensure_not_paused(env)?;
+
+    if quantity == 0 {
+        return Err(EventRegistryError::InvalidQuantity);
+    }
+    let quantity_i128 = quantity as i128;
+
+    let mut event_info =
+        storage::get_event(env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+
+    if event_info.status != EventStatus::Published {
+        return Err(EventRegistryError::EventInactive);
+    }
+
+    let new_supply = event_info
+        .current_supply
+        .checked_add(quantity_i128)
+        .ok_or(EventRegistryError::SupplyOverflow)?;
+
+    // Get and update tier
+    let mut tier = event_info
+        .tiers
+        .get(tier_id.clone())
+        .ok_or(EventRegistryError::TierNotFound)?;
+
+    if tier.is_retired {
+        return Err(EventRegistryError::TierRetired);
+    }
+    check_sales_window(env, &event_info, &tier)?;
+    release_expired_holds(env, &event_info);
+
+    let new_sold = tier
+        .current_sold
+        .checked_add(quantity_i128)
+        .ok_or(EventRegistryError::SupplyOverflow)?;
+    check_price_steps(&tier, new_sold)?;
+
+    // The paid units come out of the buyer's own hold
+    let mut reservations = active_reservations(env, &event_id);
+    if let Some(buyer) = buyer {
+        if let Some(index) = find_reservation(&reservations, &tier_id, &buyer) {
+            let mut reservation = reservations.get(index).unwrap();
+            if reservation.quantity <= quantity {
+                reservations.remove(index);
+            } else {
+                reservation.quantity -= quantity;
+                reservations.set(index, reservation);
+            }
+        }
+    }
+    check_availability(&event_info, &tier_id, &tier, &reservations, quantity_i128)?;
+
+    tier.current_sold = new_sold;
+    event_info.tiers.set(tier_id, tier);
+    event_info.current_supply = new_supply;
+
+    storage::store_event(env, event_info.clone());
+    storage::set_reservations(env, &event_id, &reservations);
+
+    env.events().publish(
+        (AgoraEvent::InventoryIncremented,),
+        InventoryIncrementedEvent {
+            event_id,
+            quantity,
+            new_supply: event_info.current_supply,
+            max_supply: event_info.max_supply,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Offers freed capacity to the tier's waitlist in order. Each buyer whose full
+/// quantity fits gets a reservation lasting `WAITLIST_OFFER_DURATION`; the queue
+/// stops at the first buyer that does not fit so nobody is skipped.
+#[allow(deprecated)]
+fn offer_to_waitlist(env: &Env, event_info: &EventInfo, tier_id: &String) {
+    let event_id = &event_info.event_id;
+    let mut waitlist = storage::get_waitlist(env, event_id, tier_id);
+    if waitlist.is_empty() || event_info.status != EventStatus::Published {
+        return;
+    }
+    let tier = match event_info.tiers.get(tier_id.clone()) {
+        Some(tier) if !tier.is_retired => tier,
+        _ => return,
+    };
+    if check_sales_window(env, event_info, &tier).is_err() {
+        return;
+    }
+
+    let mut reservations = active_reservations(env, event_id);
+    let expires_at = env.ledger().timestamp() + WAITLIST_OFFER_DURATION;
+    while let Some(entry) = waitlist.first() {
+        // The offer replaces any hold the buyer already has on the tier
+        let existing = find_reservation(&reservations, tier_id, &entry.buyer);
+        let mut available = available_units(event_info, tier_id, &tier, &reservations);
+        if let Some(index) = existing {
+            available += reservations.get(index).unwrap().quantity as i128;
+        }
+        if (entry.quantity as i128) > available {
+            break;
+        }
+
+        if let Some(index) = existing {
+            reservations.remove(index);
+        }
+        reservations.push_back(Reservation {
+            tier_id: tier_id.clone(),
+            buyer: entry.buyer.clone(),
+            quantity: entry.quantity,
+            expires_at,
+        });
+        waitlist.pop_front();
+
+        env.events().publish(
+            (AgoraEvent::WaitlistOffered,),
+            WaitlistOfferedEvent {
+                event_id: event_id.clone(),
+                tier_id: tier_id.clone(),
+                buyer: entry.buyer,
+                quantity: entry.quantity,
+                expires_at,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    storage::set_waitlist(env, event_id, tier_id, &waitlist);
+    storage::set_reservations(env, event_id, &reservations);
+}
+
+/// Drops lapsed holds and offers the capacity they free to the waitlists of their
+/// tiers, so an unused waitlist offer passes to the next buyer in line.
+fn release_expired_holds(env: &Env, event_info: &EventInfo) {
+    let now = env.ledger().timestamp();
+    let mut tiers: Vec<String> = Vec::new(env);
+    for reservation in storage::get_reservations(env, &event_info.event_id).iter() {
+        if reservation.expires_at <= now && !tiers.contains(&reservation.tier_id) {
+            tiers.push_back(reservation.tier_id);
+        }
+    }
+    if tiers.is_empty() {
+        return;
+    }
+
+    let reservations = active_reservations(env, &event_info.event_id);
+    storage::set_reservations(env, &event_info.event_id, &reservations);
+    for tier_id in tiers.iter() {
+        offer_to_waitlist(env, event_info, &tier_id);
+    }
+}
+
+/// Units of a tier that can still be reserved or sold, after the tier limit, the
+/// event max supply and every active hold.
+fn available_units(
+    event_info: &EventInfo,
+    tier_id: &String,
+    tier: &TicketTier,
+    reservations: &Vec<Reservation>,
+) -> i128 {
+    let mut available =
+        tier.tier_limit - tier.current_sold - held_units(reservations, Some(tier_id));
+    if event_info.max_supply > 0 {
+        let event_available =
+            event_info.max_supply - event_info.current_supply - held_units(reservations, None);
+        available = available.min(event_available);
+    }
+    available.max(0)
+}
+
+/// Returns the event's reservations that have not expired yet.
+fn active_reservations(env: &Env, event_id: &String) -> Vec<Reservation> {
+    let now = env.ledger().timestamp();
+    let mut active = Vec::new(env);
+    for reservation in storage::get_reservations(env, event_id).iter() {
+        if reservation.expires_at > now {
+            active.push_back(reservation);
+        }
+    }
+    active
+}
+
+/// Finds the index of a buyer's hold on a tier.
+fn find_reservation(
+    reservations: &Vec<Reservation>,
+    tier_id: &String,
+    buyer: &Address,
+) -> Option<u32> {
+    reservations
+        .iter()
+        .position(|r| &r.tier_id == tier_id && &r.buyer == buyer)
+        .map(|i| i as u32)
+}
+
+/// Sums held units, for a single tier or for the whole event when `tier_id` is None.
+fn held_units(reservations: &Vec<Reservation>, tier_id: Option<&String>) -> i128 {
+    reservations
+        .iter()
+        .filter(|r| tier_id.is_none_or(|id| &r.tier_id == id))
+        .map(|r| r.quantity as i128)
+        .sum()
+}
+
+/// Ensures `sold` additional units plus every hold fit in the tier limit and the
+/// event max supply.
+fn check_availability(
+    event_info: &EventInfo,
+    tier_id: &String,
+    tier: &TicketTier,
+    reservations: &Vec<Reservation>,
+    sold: i128,
+) -> Result<(), EventRegistryError> {
+    let event_total = event_info.current_supply + sold + held_units(reservations, None);
+    if event_info.max_supply > 0 && event_total > event_info.max_supply {
+        return Err(EventRegistryError::MaxSupplyExceeded);
+    }
+    let tier_total = tier.current_sold + sold + held_units(reservations, Some(tier_id));
+    if tier_total > tier.tier_limit {
+        return Err(EventRegistryError::TierSupplyExceeded);
+    }
+    Ok(())
+}
+
+/// Rejects purchases before the tier's sales open or after they close. Sales close at
+/// the tier's `sale_end`, or when the event starts if it has none, and never run past
+/// the end of the event.
+fn check_sales_window(
+    env: &Env,
+    event_info: &EventInfo,
+    tier: &TicketTier,
+) -> Result<(), EventRegistryError> {
+    let now = env.ledger().timestamp();
+    if now < tier.sale_start {
+        return Err(EventRegistryError::SalesNotStarted);
+    }
+    // Without an end of its own a tier stops selling once doors open. An end of 0
+    // means no bound (legacy records have no event times)
+    let sale_end = if tier.sale_end != 0 {
+        tier.sale_end
+    } else {
+        event_info.start_time
+    };
+    let ended = |end: u64| end != 0 && now >= end;
+    if ended(sale_end) || ended(event_info.end_time) {
+        return Err(EventRegistryError::SalesEnded);
+    }
+    Ok(())
+}
+
+/// Ensures a fixed promo discount leaves every tier it covers with a positive price,
+/// at the base price and at every step of the tier's price schedule.
+fn validate_fixed_discount(
+    tiers: &Map<String, TicketTier>,
+    code_tiers: &Vec<String>,
+    amount: i128,
+) -> Result<(), EventRegistryError> {
+    for (tier_id, tier) in tiers.iter() {
+        if !code_tiers.is_empty() && !code_tiers.contains(&tier_id) {
+            continue;
+        }
+        let lowest = tier
+            .price_schedule
+            .iter()
+            .map(|step| step.price)
+            .fold(tier.price, i128::min);
+        if amount >= lowest {
+            return Err(EventRegistryError::InvalidDiscount);
+        }
+    }
+    Ok(())
+}
+
+/// Re-checks the event's fixed promo discounts after its tiers change.
+fn validate_promo_codes(env: &Env, event_info: &EventInfo) -> Result<(), EventRegistryError> {
+    for code_hash in storage::get_event_promo_codes(env, &event_info.event_id).iter() {
+        if let Some(promo_code) = storage::get_promo_code(env, &event_info.event_id, &code_hash) {
+            if let Discount::Fixed(amount) = promo_code.discount {
+                validate_fixed_discount(&event_info.tiers, &promo_code.tiers, amount)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Ensures the sum of all tier limits, retired tiers included, fits in `max_supply`.
+fn validate_tier_limits(
+    tiers: &Map<String, TicketTier>,
+    max_supply: i128,
+) -> Result<(), EventRegistryError> {
+    // max_supply of 0 means unlimited
+    if max_supply > 0 {
+        let mut total_tier_limit: i128 = 0;
+        for tier in tiers.values() {
+            total_tier_limit = total_tier_limit
+                .checked_add(tier.tier_limit)
+                .ok_or(EventRegistryError::SupplyOverflow)?;
+        }
+        if total_tier_limit > max_supply {
+            return Err(EventRegistryError::TierLimitExceedsMaxSupply);
+        }
     }
     Ok(())
 }
@@ -1,5 +1,8 @@
-use crate::types::{DataKey, EventInfo};
-use soroban_sdk::{Address, Env, String, Vec};
+use crate::types::{
+    DataKey, EventInfo, EventRole, FeeOverride, FeeOverrideTarget, PendingEventTransfer, PromoCode,
+    Reservation, WaitlistEntry,
+};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
 /// Sets the administrator address of the contract.
 pub fn set_admin(env: &Env, admin: &Address) {
@@ -11,6 +14,36 @@ pub fn get_admin(env: &Env) -> Option<Address> {
     env.storage().persistent().get(&DataKey::Admin)
 }
 
+/// Sets the administrator waiting to accept the role.
+pub fn set_pending_admin(env: &Env, admin: &Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingAdmin, admin);
+}
+
+/// Retrieves the administrator waiting to accept the role, if any.
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::PendingAdmin)
+}
+
+/// Clears the pending administrator.
+pub fn remove_pending_admin(env: &Env) {
+    env.storage().persistent().remove(&DataKey::PendingAdmin);
+}
+
+/// Sets the emergency pause flag.
+pub fn set_paused(env: &Env, paused: bool) {
+    env.storage().persistent().set(&DataKey::Paused, &paused);
+}
+
+/// Checks if the contract is paused.
+pub fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Paused)
+        .unwrap_or(false)
+}
+
 /// Sets the platform wallet address of the contract.
 pub fn set_platform_wallet(env: &Env, wallet: &Address) {
     env.storage()
@@ -105,6 +138,19 @@ pub fn get_organizer_events(env: &Env, organizer: &Address) -> Vec<String> {
         .unwrap_or_else(|| Vec::new(env))
 }
 
+/// Removes an event_id from an organizer's list of events.
+pub fn remove_organizer_event(env: &Env, organizer: &Address, event_id: &String) {
+    let organizer_events = get_organizer_events(env, organizer);
+    if let Some(index) = organizer_events.first_index_of(event_id) {
+        let mut organizer_events = organizer_events;
+        organizer_events.remove(index);
+        env.storage().persistent().set(
+            &DataKey::OrganizerEvents(organizer.clone()),
+            &organizer_events,
+        );
+    }
+}
+
 /// Sets the authorized TicketPayment contract address.
 pub fn set_ticket_payment_contract(env: &Env, address: &Address) {
     env.storage()
@@ -118,3 +164,238 @@ pub fn get_ticket_payment_contract(env: &Env) -> Option<Address> {
         .persistent()
         .get(&DataKey::TicketPaymentContract)
 }
+
+/// Sets the role of an address on an event and records it in the event's role holders.
+pub fn set_event_role(env: &Env, event_id: String, account: &Address, role: EventRole) {
+    env.storage().persistent().set(
+        &DataKey::EventRole(event_id.clone(), account.clone()),
+        &role,
+    );
+
+    let mut holders = get_event_role_holders(env, event_id.clone());
+    if !holders.contains(account) {
+        holders.push_back(account.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::EventRoleHolders(event_id), &holders);
+    }
+}
+
+/// Retrieves the role granted to an address on an event, if any.
+pub fn get_event_role(env: &Env, event_id: String, account: &Address) -> Option<EventRole> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EventRole(event_id, account.clone()))
+}
+
+/// Removes the role of an address on an event.
+pub fn remove_event_role(env: &Env, event_id: String, account: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::EventRole(event_id.clone(), account.clone()));
+
+    let holders = get_event_role_holders(env, event_id.clone());
+    if let Some(index) = holders.first_index_of(account) {
+        let mut holders = holders;
+        holders.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::EventRoleHolders(event_id), &holders);
+    }
+}
+
+/// Retrieves every address holding a role on an event.
+pub fn get_event_role_holders(env: &Env, event_id: String) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EventRoleHolders(event_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Removes every role granted on an event.
+pub fn clear_event_roles(env: &Env, event_id: String) {
+    for account in get_event_role_holders(env, event_id.clone()).iter() {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::EventRole(event_id.clone(), account));
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::EventRoleHolders(event_id));
+}
+
+/// Stores the pending ownership transfer of an event.
+pub fn set_pending_event_transfer(env: &Env, event_id: String, transfer: &PendingEventTransfer) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingEventTransfer(event_id), transfer);
+}
+
+/// Retrieves the pending ownership transfer of an event, if any.
+pub fn get_pending_event_transfer(env: &Env, event_id: String) -> Option<PendingEventTransfer> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingEventTransfer(event_id))
+}
+
+/// Clears the pending ownership transfer of an event.
+pub fn remove_pending_event_transfer(env: &Env, event_id: String) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingEventTransfer(event_id));
+}
+
+/// Stores a fee override and records its target in the override list.
+pub fn set_fee_override(env: &Env, fee_override: &FeeOverride) {
+    let target = fee_override.target.clone();
+    env.storage()
+        .persistent()
+        .set(&DataKey::FeeOverride(target.clone()), fee_override);
+
+    let mut targets = get_fee_override_targets(env);
+    if !targets.contains(&target) {
+        targets.push_back(target);
+        env.storage()
+            .persistent()
+            .set(&DataKey::FeeOverrideTargets, &targets);
+    }
+}
+
+/// Retrieves the fee override of a target, expired or not.
+pub fn get_fee_override(env: &Env, target: &FeeOverrideTarget) -> Option<FeeOverride> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FeeOverride(target.clone()))
+}
+
+/// Removes the fee override of a target.
+pub fn remove_fee_override(env: &Env, target: &FeeOverrideTarget) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::FeeOverride(target.clone()));
+
+    let targets = get_fee_override_targets(env);
+    if let Some(index) = targets.first_index_of(target) {
+        let mut targets = targets;
+        targets.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::FeeOverrideTargets, &targets);
+    }
+}
+
+/// Retrieves every target that has a fee override.
+pub fn get_fee_override_targets(env: &Env) -> Vec<FeeOverrideTarget> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FeeOverrideTargets)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Stores the reservations of an event, removing the entry once none are left.
+pub fn set_reservations(env: &Env, event_id: &String, reservations: &Vec<Reservation>) {
+    let key = DataKey::Reservations(event_id.clone());
+    if reservations.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, reservations);
+    }
+}
+
+/// Retrieves every stored reservation of an event, expired or not.
+pub fn get_reservations(env: &Env, event_id: &String) -> Vec<Reservation> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Reservations(event_id.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Stores a tier's waitlist, removing the entry once nobody is waiting.
+pub fn set_waitlist(env: &Env, event_id: &String, tier_id: &String, waitlist: &Vec<WaitlistEntry>) {
+    let key = DataKey::Waitlist(event_id.clone(), tier_id.clone());
+    if waitlist.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, waitlist);
+    }
+}
+
+/// Retrieves a tier's waitlist, first in line first.
+pub fn get_waitlist(env: &Env, event_id: &String, tier_id: &String) -> Vec<WaitlistEntry> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Waitlist(event_id.clone(), tier_id.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Stores a promo code of an event and records it in the event's promo code list.
+pub fn set_promo_code(env: &Env, event_id: &String, promo_code: &PromoCode) {
+    env.storage().persistent().set(
+        &DataKey::PromoCode(event_id.clone(), promo_code.code_hash.clone()),
+        promo_code,
+    );
+
+    let mut code_hashes = get_event_promo_codes(env, event_id);
+    if !code_hashes.contains(&promo_code.code_hash) {
+        code_hashes.push_back(promo_code.code_hash.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::EventPromoCodes(event_id.clone()), &code_hashes);
+    }
+}
+
+/// Retrieves a promo code of an event by its hash.
+pub fn get_promo_code(env: &Env, event_id: &String, code_hash: &BytesN<32>) -> Option<PromoCode> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PromoCode(event_id.clone(), code_hash.clone()))
+}
+
+/// Removes a promo code of an event.
+pub fn remove_promo_code(env: &Env, event_id: &String, code_hash: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PromoCode(event_id.clone(), code_hash.clone()));
+
+    let code_hashes = get_event_promo_codes(env, event_id);
+    if let Some(index) = code_hashes.first_index_of(code_hash) {
+        let mut code_hashes = code_hashes;
+        code_hashes.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::EventPromoCodes(event_id.clone()), &code_hashes);
+    }
+}
+
+/// Retrieves the hashes of every promo code of an event.
+pub fn get_event_promo_codes(env: &Env, event_id: &String) -> Vec<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EventPromoCodes(event_id.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Adds an account to, or removes it from, a tier's allowlist.
+pub fn set_allowlisted(
+    env: &Env,
+    event_id: &String,
+    tier_id: &String,
+    account: &Address,
+    allowed: bool,
+) {
+    let key = DataKey::TierAllowlist(event_id.clone(), tier_id.clone(), account.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Checks whether an account is on a tier's allowlist.
+pub fn is_allowlisted(env: &Env, event_id: &String, tier_id: &String, account: &Address) -> bool {
+    env.storage().persistent().has(&DataKey::TierAllowlist(
+        event_id.clone(),
+        tier_id.clone(),
+        account.clone(),
+    ))
+}
@@ -1,7 +1,13 @@
 use super::*;
 use crate::error::EventRegistryError;
-use crate::types::{EventInfo, TicketTier};
-use soroban_sdk::{testutils::Address as _, Address, Env, Map, String};
+use crate::types::{
+    AccessRule, Discount, EventInfo, EventRole, EventStatus, FeeOverrideTarget, PriceStep,
+    PriceTrigger, RefundWindow, TicketTier,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Map, String, Vec,
+};
 
 #[test]
 fn test_initialize() {
@@ -104,6 +110,7 @@ fn test_set_platform_fee_unauthorized() {
 #[test]
 fn test_storage_operations() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
 
@@ -121,18 +128,21 @@ fn test_storage_operations() {
         organizer_address: organizer.clone(),
         payment_address: payment_address.clone(),
         platform_fee_percent: 5,
-        is_active: true,
+        status: EventStatus::Published,
         created_at: env.ledger().timestamp(),
+        start_time: 0,
+        end_time: 1_000_000,
         metadata_cid: String::from_str(
             &env,
             "bafkreifh22222222222222222222222222222222222222222222222222",
         ),
         max_supply: 100,
         current_supply: 0,
+        max_per_buyer: 0,
         tiers,
     };
 
-    client.store_event(&event_info);
+    client.import_events(&soroban_sdk::vec![&env, event_info]);
 
     assert!(client.event_exists(&event_id));
 
@@ -141,7 +151,7 @@ fn test_storage_operations() {
     assert_eq!(stored_event.organizer_address, organizer);
     assert_eq!(stored_event.payment_address, payment_address);
     assert_eq!(stored_event.platform_fee_percent, 5);
-    assert!(stored_event.is_active);
+    assert_eq!(stored_event.status, EventStatus::Published);
     assert_eq!(stored_event.max_supply, 100);
     assert_eq!(stored_event.current_supply, 0);
 
@@ -163,14 +173,17 @@ fn test_organizer_events_list() {
         organizer_address: organizer.clone(),
         payment_address: payment_address.clone(),
         platform_fee_percent: 5,
-        is_active: true,
+        status: EventStatus::Published,
         created_at: 100,
+        start_time: 0,
+        end_time: 1_000_000,
         metadata_cid: String::from_str(
             &env,
             "bafkreifh22222222222222222222222222222222222222222222222222",
         ),
         max_supply: 50,
         current_supply: 0,
+        max_per_buyer: 0,
         tiers: tiers.clone(),
     };
 
@@ -179,22 +192,26 @@ fn test_organizer_events_list() {
         organizer_address: organizer.clone(),
         payment_address: payment_address.clone(),
         platform_fee_percent: 5,
-        is_active: true,
+        status: EventStatus::Published,
         created_at: 200,
+        start_time: 0,
+        end_time: 1_000_000,
         metadata_cid: String::from_str(
             &env,
             "bafkreifh22222222222222222222222222222222222222222222222222",
         ),
         max_supply: 0,
         current_supply: 0,
+        max_per_buyer: 0,
         tiers,
     };
 
     let contract_id = env.register(EventRegistry, ());
     let client = EventRegistryClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
 
-    client.store_event(&event_1);
-    client.store_event(&event_2);
+    client.import_events(&soroban_sdk::vec![&env, event_1.clone(), event_2.clone()]);
 
     let organizer_events = client.get_organizer_events(&organizer);
     assert_eq!(organizer_events.len(), 2);
@@ -231,6 +248,15 @@ fn test_register_event_success() {
             tier_limit: 100,
             current_sold: 0,
             is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
         },
     );
 
@@ -241,6 +267,9 @@ fn test_register_event_success() {
         &metadata_cid,
         &100,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     let payment_info = client.get_event_payment_info(&event_id);
@@ -280,6 +309,9 @@ fn test_register_event_unlimited_supply() {
         &metadata_cid,
         &0,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     let event_info = client.get_event(&event_id).unwrap();
@@ -314,6 +346,9 @@ fn test_register_duplicate_event_fails() {
         &metadata_cid,
         &100,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     let result = client.try_register_event(
@@ -323,6 +358,9 @@ fn test_register_duplicate_event_fails() {
         &metadata_cid,
         &100,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
     assert_eq!(result, Err(Ok(EventRegistryError::EventAlreadyExists)));
 }
@@ -354,6 +392,9 @@ fn test_get_event_payment_info() {
         &metadata_cid,
         &50,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     let info = client.get_event_payment_info(&event_id);
@@ -388,11 +429,14 @@ fn test_update_event_status() {
         &metadata_cid,
         &100,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
-    client.update_event_status(&event_id, &false);
+    client.update_event_status(&organizer, &event_id, &EventStatus::Cancelled);
 
     let event_info = client.get_event(&event_id).unwrap();
-    assert!(!event_info.is_active);
+    assert_eq!(event_info.status, EventStatus::Cancelled);
 }
 
 #[test]
@@ -421,8 +465,11 @@ fn test_event_inactive_error() {
         &metadata_cid,
         &100,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
-    client.update_event_status(&event_id, &false);
+    client.update_event_status(&organizer, &event_id, &EventStatus::Cancelled);
 
     let result = client.try_get_event_payment_info(&event_id);
     assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
@@ -455,6 +502,9 @@ fn test_complete_event_lifecycle() {
         &metadata_cid,
         &200,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     let payment_info = client.get_event_payment_info(&event_id);
@@ -465,13 +515,13 @@ fn test_complete_event_lifecycle() {
     assert_eq!(org_events.len(), 1);
     assert!(org_events.contains(&event_id));
 
-    client.update_event_status(&event_id, &false);
+    client.update_event_status(&organizer, &event_id, &EventStatus::Cancelled);
 
     let result = client.try_get_event_payment_info(&event_id);
     assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
 
     let event_info = client.get_event(&event_id).unwrap();
-    assert!(!event_info.is_active);
+    assert_eq!(event_info.status, EventStatus::Cancelled);
 }
 
 #[test]
@@ -501,13 +551,16 @@ fn test_update_metadata_success() {
         &metadata_cid,
         &100,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     let new_metadata_cid = String::from_str(
         &env,
         "bafkreifh22222222222222222222222222222222222222222222222222",
     );
-    client.update_metadata(&event_id, &new_metadata_cid);
+    client.update_metadata(&organizer, &event_id, &new_metadata_cid);
 
     let event_info = client.get_event(&event_id).unwrap();
     assert_eq!(event_info.metadata_cid, new_metadata_cid);
@@ -540,20 +593,23 @@ fn test_update_metadata_invalid_cid() {
         &metadata_cid,
         &100,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     let wrong_char_cid = String::from_str(
         &env,
         "Qafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
     );
-    let result_wrong_char = client.try_update_metadata(&event_id, &wrong_char_cid);
+    let result_wrong_char = client.try_update_metadata(&organizer, &event_id, &wrong_char_cid);
     assert_eq!(
         result_wrong_char,
         Err(Ok(EventRegistryError::InvalidMetadataCid))
     );
 
     let short_cid = String::from_str(&env, "bafy");
-    let result = client.try_update_metadata(&event_id, &short_cid);
+    let result = client.try_update_metadata(&organizer, &event_id, &short_cid);
     assert_eq!(result, Err(Ok(EventRegistryError::InvalidMetadataCid)));
 }
 
@@ -610,6 +666,15 @@ fn test_increment_inventory_success() {
             tier_limit: 10,
             current_sold: 0,
             is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
         },
     );
 
@@ -620,6 +685,9 @@ fn test_increment_inventory_success() {
         &metadata_cid,
         &10,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     client.increment_inventory(&event_id, &tier_id);
@@ -671,6 +739,15 @@ fn test_increment_inventory_max_supply_exceeded() {
             tier_limit: 2,
             current_sold: 0,
             is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
         },
     );
 
@@ -681,6 +758,9 @@ fn test_increment_inventory_max_supply_exceeded() {
         &metadata_cid,
         &2,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     client.increment_inventory(&event_id, &tier_id);
@@ -727,6 +807,15 @@ fn test_increment_inventory_unlimited_supply() {
             tier_limit: 1000,
             current_sold: 0,
             is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
         },
     );
 
@@ -737,6 +826,9 @@ fn test_increment_inventory_unlimited_supply() {
         &metadata_cid,
         &0,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     for _ in 0..10 {
@@ -801,6 +893,15 @@ fn test_increment_inventory_inactive_event() {
             tier_limit: 100,
             current_sold: 0,
             is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
         },
     );
     client.register_event(
@@ -810,9 +911,12 @@ fn test_increment_inventory_inactive_event() {
         &metadata_cid,
         &100,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
-    client.update_event_status(&event_id, &false);
+    client.update_event_status(&organizer, &event_id, &EventStatus::SalesPaused);
 
     let result = client.try_increment_inventory(&event_id, &tier_id);
     assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
@@ -850,6 +954,15 @@ fn test_increment_inventory_persists_across_reads() {
             tier_limit: 50,
             current_sold: 0,
             is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
         },
     );
     client.register_event(
@@ -859,6 +972,9 @@ fn test_increment_inventory_persists_across_reads() {
         &metadata_cid,
         &50,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     for _ in 0..5 {
@@ -904,6 +1020,15 @@ fn test_tier_limit_exceeds_max_supply() {
             tier_limit: 60,
             current_sold: 0,
             is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
         },
     );
     tiers.set(
@@ -914,6 +1039,15 @@ fn test_tier_limit_exceeds_max_supply() {
             tier_limit: 50,
             current_sold: 0,
             is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
         },
     );
 
@@ -924,6 +1058,9 @@ fn test_tier_limit_exceeds_max_supply() {
         &metadata_cid,
         &100,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
     assert_eq!(
         result,
@@ -963,6 +1100,15 @@ fn test_tier_not_found() {
             tier_limit: 100,
             current_sold: 0,
             is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
         },
     );
 
@@ -973,6 +1119,9 @@ fn test_tier_not_found() {
         &metadata_cid,
         &100,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     let wrong_tier_id = String::from_str(&env, "nonexistent");
@@ -1013,6 +1162,15 @@ fn test_tier_supply_exceeded() {
             tier_limit: 3,
             current_sold: 0,
             is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
         },
     );
 
@@ -1023,6 +1181,9 @@ fn test_tier_supply_exceeded() {
         &metadata_cid,
         &100,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     client.increment_inventory(&event_id, &tier_id);
@@ -1068,6 +1229,15 @@ fn test_multiple_tiers_inventory() {
             tier_limit: 50,
             current_sold: 0,
             is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
         },
     );
     tiers.set(
@@ -1078,6 +1248,15 @@ fn test_multiple_tiers_inventory() {
             tier_limit: 20,
             current_sold: 0,
             is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
         },
     );
 
@@ -1088,6 +1267,9 @@ fn test_multiple_tiers_inventory() {
         &metadata_cid,
         &70,
         &tiers,
+        &0,
+        &1_000_000,
+        &true,
     );
 
     client.increment_inventory(&event_id, &general_id);
@@ -1103,3 +1285,1543 @@ fn test_multiple_tiers_inventory() {
     let vip_tier = event_info.tiers.get(vip_id).unwrap();
     assert_eq!(vip_tier.current_sold, 1);
 }
+
+// ==================== Bulk Inventory Tests ====================
+
+fn setup_bulk_event(
+    env: &Env,
+    tier_limit: i128,
+    max_supply: i128,
+) -> (EventRegistryClient<'static>, String, String, Address) {
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let organizer = Address::generate(env);
+    let payment_addr = Address::generate(env);
+    let platform_wallet = Address::generate(env);
+    let ticket_payment = Address::generate(env);
+
+    client.initialize(&admin, &platform_wallet, &500);
+    client.set_ticket_payment_contract(&ticket_payment);
+
+    let event_id = String::from_str(env, "bulk_event");
+    let metadata_cid = String::from_str(
+        env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+
+    let mut tiers = Map::new(env);
+    let tier_id = String::from_str(env, "general");
+    tiers.set(
+        tier_id.clone(),
+        TicketTier {
+            name: String::from_str(env, "General"),
+            price: 5000000,
+            tier_limit,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule: Vec::new(env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
+        },
+    );
+
+    client.register_event(
+        &event_id,
+        &organizer,
+        &payment_addr,
+        &metadata_cid,
+        &max_supply,
+        &tiers,
+        &0,
+        &1_000_000,
+        &true,
+    );
+
+    (client, event_id, tier_id, organizer)
+}
+
+#[test]
+fn test_increment_inventory_bulk_success() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, _) = setup_bulk_event(&env, 10, 10);
+
+    client.increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &4);
+    client.increment_inventory(&event_id, &tier_id);
+
+    let event_info = client.get_event(&event_id).unwrap();
+    assert_eq!(event_info.current_supply, 5);
+    assert_eq!(event_info.tiers.get(tier_id).unwrap().current_sold, 5);
+}
+
+#[test]
+fn test_increment_inventory_bulk_checks_whole_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, _) = setup_bulk_event(&env, 3, 0);
+
+    client.increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &2);
+
+    let result =
+        client.try_increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &2);
+    assert_eq!(result, Err(Ok(EventRegistryError::TierSupplyExceeded)));
+
+    let event_info = client.get_event(&event_id).unwrap();
+    assert_eq!(event_info.current_supply, 2);
+    assert_eq!(event_info.tiers.get(tier_id).unwrap().current_sold, 2);
+}
+
+#[test]
+fn test_increment_inventory_bulk_max_supply_exceeded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, _) = setup_bulk_event(&env, 5, 5);
+
+    client.increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &4);
+
+    let result =
+        client.try_increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &2);
+    assert_eq!(result, Err(Ok(EventRegistryError::MaxSupplyExceeded)));
+}
+
+#[test]
+fn test_increment_inventory_bulk_zero_quantity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, _) = setup_bulk_event(&env, 5, 5);
+
+    let result =
+        client.try_increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &0);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidQuantity)));
+}
+
+#[test]
+fn test_decrement_inventory_bulk() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, _) = setup_bulk_event(&env, 10, 10);
+
+    client.increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &6);
+    client.decrement_inventory_bulk(&event_id, &tier_id, &4);
+
+    let event_info = client.get_event(&event_id).unwrap();
+    assert_eq!(event_info.current_supply, 2);
+    assert_eq!(
+        event_info.tiers.get(tier_id.clone()).unwrap().current_sold,
+        2
+    );
+
+    let result = client.try_decrement_inventory_bulk(&event_id, &tier_id, &3);
+    assert_eq!(result, Err(Ok(EventRegistryError::SupplyUnderflow)));
+}
+
+// ==================== Refund Schedule Tests ====================
+
+#[test]
+fn test_register_event_with_refund_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let mut refund_schedule = Vec::new(&env);
+    refund_schedule.push_back(RefundWindow {
+        deadline: 1000,
+        refund_bps: 10000,
+    });
+    refund_schedule.push_back(RefundWindow {
+        deadline: 2000,
+        refund_bps: 5000,
+    });
+
+    let tier_id = String::from_str(&env, "general");
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        tier_id.clone(),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 5000000,
+            tier_limit: 10,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule: refund_schedule.clone(),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
+        },
+    );
+
+    let event_id = String::from_str(&env, "scheduled_event");
+    client.register_event(
+        &event_id,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        &10,
+        &tiers,
+        &0,
+        &1_000_000,
+        &true,
+    );
+
+    let stored = client.get_event(&event_id).unwrap();
+    assert_eq!(
+        stored.tiers.get(tier_id).unwrap().refund_schedule,
+        refund_schedule
+    );
+}
+
+#[test]
+fn test_register_event_invalid_refund_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let organizer = Address::generate(&env);
+    let metadata_cid = String::from_str(
+        &env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+
+    let schedules = [
+        // Deadlines out of order
+        ("bad_order", [(2000u64, 10000u32), (1000u64, 5000u32)]),
+        // Refund above 100%
+        ("bad_bps", [(1000u64, 10001u32), (2000u64, 5000u32)]),
+    ];
+
+    for (event_id, steps) in schedules.iter() {
+        let mut refund_schedule = Vec::new(&env);
+        for (deadline, refund_bps) in steps.iter() {
+            refund_schedule.push_back(RefundWindow {
+                deadline: *deadline,
+                refund_bps: *refund_bps,
+            });
+        }
+        let mut tiers = Map::new(&env);
+        tiers.set(
+            String::from_str(&env, "general"),
+            TicketTier {
+                name: String::from_str(&env, "General"),
+                price: 5000000,
+                tier_limit: 10,
+                current_sold: 0,
+                is_refundable: true,
+                refund_schedule,
+                max_resale_bps: 0,
+                royalty_bps: 0,
+                sale_start: 0,
+                sale_end: 0,
+                price_schedule: Vec::new(&env),
+                is_retired: false,
+                max_per_buyer: 0,
+                access_rule: AccessRule::Open,
+            },
+        );
+
+        let result = client.try_register_event(
+            &String::from_str(&env, event_id),
+            &organizer,
+            &organizer,
+            &metadata_cid,
+            &10,
+            &tiers,
+            &0,
+            &1_000_000,
+            &true,
+        );
+        assert_eq!(result, Err(Ok(EventRegistryError::InvalidRefundSchedule)));
+    }
+}
+
+#[test]
+fn test_register_event_end_time_in_past() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(5000);
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let organizer = Address::generate(&env);
+    let result = client.try_register_event(
+        &String::from_str(&env, "past_event"),
+        &organizer,
+        &organizer,
+        &String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        &10,
+        &Map::new(&env),
+        &0,
+        &5000,
+        &true,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidEventTime)));
+}
+
+#[test]
+fn test_set_resale_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, organizer) = setup_bulk_event(&env, 10, 0);
+
+    client.set_resale_policy(&organizer, &event_id, &tier_id, &11000, &500);
+    let tier = client
+        .get_event(&event_id)
+        .unwrap()
+        .tiers
+        .get(tier_id.clone())
+        .unwrap();
+    assert_eq!(tier.max_resale_bps, 11000);
+    assert_eq!(tier.royalty_bps, 500);
+
+    let result = client.try_set_resale_policy(&organizer, &event_id, &tier_id, &11000, &10001);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidResalePolicy)));
+
+    let result = client.try_set_resale_policy(
+        &organizer,
+        &event_id,
+        &String::from_str(&env, "vip"),
+        &11000,
+        &500,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::TierNotFound)));
+}
+
+#[test]
+fn test_increment_inventory_respects_sales_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+    client.set_ticket_payment_contract(&Address::generate(&env));
+
+    let organizer = Address::generate(&env);
+    let event_id = String::from_str(&env, "drop_event");
+    let tier_id = String::from_str(&env, "drop");
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        tier_id.clone(),
+        TicketTier {
+            name: String::from_str(&env, "Drop"),
+            price: 1000,
+            tier_limit: 10,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 1000,
+            sale_end: 2000,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
+        },
+    );
+    let general = String::from_str(&env, "general");
+    tiers.set(general.clone(), basic_tier(&env, 1000, 10));
+    let door = String::from_str(&env, "door");
+    let mut door_tier = basic_tier(&env, 1000, 10);
+    door_tier.sale_end = 2500;
+    tiers.set(door.clone(), door_tier);
+    client.register_event(
+        &event_id,
+        &organizer,
+        &organizer,
+        &String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        &0,
+        &tiers,
+        &2000,
+        &3000,
+        &true,
+    );
+    let event = client.get_event(&event_id).unwrap();
+    assert_eq!(event.start_time, 2000);
+    assert_eq!(event.end_time, 3000);
+
+    let result = client.try_increment_inventory(&event_id, &tier_id);
+    assert_eq!(result, Err(Ok(EventRegistryError::SalesNotStarted)));
+
+    env.ledger().set_timestamp(1500);
+    client.increment_inventory(&event_id, &tier_id);
+
+    // Sales close once doors open
+    env.ledger().set_timestamp(2000);
+    let result = client.try_increment_inventory(&event_id, &tier_id);
+    assert_eq!(result, Err(Ok(EventRegistryError::SalesEnded)));
+
+    // ...also for tiers without an end of their own, unless they set a later one
+    let result = client.try_increment_inventory(&event_id, &general);
+    assert_eq!(result, Err(Ok(EventRegistryError::SalesEnded)));
+    client.increment_inventory(&event_id, &door);
+    env.ledger().set_timestamp(2500);
+    let result = client.try_increment_inventory(&event_id, &door);
+    assert_eq!(result, Err(Ok(EventRegistryError::SalesEnded)));
+}
+
+#[test]
+fn test_register_event_invalid_sales_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let organizer = Address::generate(&env);
+    let metadata_cid = String::from_str(
+        &env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        String::from_str(&env, "general"),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 1000,
+            tier_limit: 10,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule: Vec::new(&env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 2000,
+            sale_end: 1000,
+            price_schedule: Vec::new(&env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
+        },
+    );
+    let result = client.try_register_event(
+        &String::from_str(&env, "event_1"),
+        &organizer,
+        &organizer,
+        &metadata_cid,
+        &0,
+        &tiers,
+        &0,
+        &5000,
+        &true,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidSalesWindow)));
+
+    // The event cannot start after it ends
+    let result = client.try_register_event(
+        &String::from_str(&env, "event_2"),
+        &organizer,
+        &organizer,
+        &metadata_cid,
+        &0,
+        &Map::new(&env),
+        &5000,
+        &4000,
+        &true,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidEventTime)));
+}
+
+fn register_priced_event(
+    env: &Env,
+    client: &EventRegistryClient,
+    event_id: &String,
+    price_schedule: Vec<PriceStep>,
+) -> Result<(), EventRegistryError> {
+    let organizer = Address::generate(env);
+    let mut tiers = Map::new(env);
+    tiers.set(
+        String::from_str(env, "general"),
+        TicketTier {
+            name: String::from_str(env, "General"),
+            price: 1000,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule: Vec::new(env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule,
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: AccessRule::Open,
+        },
+    );
+    match client.try_register_event(
+        event_id,
+        &organizer,
+        &organizer,
+        &String::from_str(
+            env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        &0,
+        &tiers,
+        &0,
+        &1_000_000,
+        &true,
+    ) {
+        Err(Ok(err)) => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[test]
+fn test_get_current_price_follows_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+    client.set_ticket_payment_contract(&Address::generate(&env));
+
+    // Early-bird ends at t=1000, then the price rises again after 2 sales
+    let mut schedule = Vec::new(&env);
+    schedule.push_back(PriceStep {
+        trigger: PriceTrigger::Time(1000),
+        price: 1500,
+    });
+    schedule.push_back(PriceStep {
+        trigger: PriceTrigger::Sold(2),
+        price: 2000,
+    });
+    let event_id = String::from_str(&env, "priced");
+    register_priced_event(&env, &client, &event_id, schedule).unwrap();
+    let tier_id = String::from_str(&env, "general");
+
+    assert_eq!(client.get_current_price(&event_id, &tier_id), 1000);
+
+    env.ledger().set_timestamp(1000);
+    assert_eq!(client.get_current_price(&event_id, &tier_id), 1500);
+
+    // A batch cannot run past the sold-count step at the lower price
+    let buyer = Address::generate(&env);
+    let result = client.try_increment_inventory_bulk(&event_id, &tier_id, &buyer, &3);
+    assert_eq!(result, Err(Ok(EventRegistryError::PriceStepCrossed)));
+
+    client.increment_inventory_bulk(&event_id, &tier_id, &buyer, &2);
+    assert_eq!(client.get_current_price(&event_id, &tier_id), 2000);
+    client.increment_inventory_bulk(&event_id, &tier_id, &buyer, &3);
+
+    let result = client.try_get_current_price(&event_id, &String::from_str(&env, "vip"));
+    assert_eq!(result, Err(Ok(EventRegistryError::TierNotFound)));
+}
+
+#[test]
+fn test_register_event_invalid_price_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let bad_schedules = [
+        [
+            (PriceTrigger::Time(2000), 1500),
+            (PriceTrigger::Time(1000), 2000),
+        ],
+        [
+            (PriceTrigger::Sold(10), 1500),
+            (PriceTrigger::Sold(10), 2000),
+        ],
+        [
+            (PriceTrigger::Time(1000), 0),
+            (PriceTrigger::Sold(10), 2000),
+        ],
+    ];
+    for steps in bad_schedules.iter() {
+        let mut schedule = Vec::new(&env);
+        for (trigger, price) in steps.iter() {
+            schedule.push_back(PriceStep {
+                trigger: trigger.clone(),
+                price: *price,
+            });
+        }
+        let result =
+            register_priced_event(&env, &client, &String::from_str(&env, "priced"), schedule);
+        assert_eq!(result, Err(EventRegistryError::InvalidPriceSchedule));
+    }
+}
+
+fn basic_tier(env: &Env, price: i128, tier_limit: i128) -> TicketTier {
+    TicketTier {
+        name: String::from_str(env, "Extra"),
+        price,
+        tier_limit,
+        current_sold: 0,
+        is_refundable: true,
+        refund_schedule: Vec::new(env),
+        max_resale_bps: 0,
+        royalty_bps: 0,
+        sale_start: 0,
+        sale_end: 0,
+        price_schedule: Vec::new(env),
+        is_retired: false,
+        max_per_buyer: 0,
+        access_rule: AccessRule::Open,
+    }
+}
+
+#[test]
+fn test_add_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, organizer) = setup_bulk_event(&env, 60, 100);
+    let vip = String::from_str(&env, "vip");
+
+    // current_sold passed by the organizer is ignored
+    let mut tier = basic_tier(&env, 9000, 40);
+    tier.current_sold = 25;
+    client.add_tier(&organizer, &event_id, &vip, &tier);
+    let stored = client
+        .get_event(&event_id)
+        .unwrap()
+        .tiers
+        .get(vip.clone())
+        .unwrap();
+    assert_eq!(stored.tier_limit, 40);
+    assert_eq!(stored.current_sold, 0);
+
+    let result = client.try_add_tier(&organizer, &event_id, &tier_id, &basic_tier(&env, 9000, 1));
+    assert_eq!(result, Err(Ok(EventRegistryError::TierAlreadyExists)));
+
+    // 60 + 40 already uses the whole max supply
+    let result = client.try_add_tier(
+        &organizer,
+        &event_id,
+        &String::from_str(&env, "balcony"),
+        &basic_tier(&env, 9000, 1),
+    );
+    assert_eq!(
+        result,
+        Err(Ok(EventRegistryError::TierLimitExceedsMaxSupply))
+    );
+}
+
+#[test]
+fn test_update_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, organizer) = setup_bulk_event(&env, 60, 100);
+    client.increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &10);
+
+    client.update_tier(&organizer, &event_id, &tier_id, &basic_tier(&env, 7000, 80));
+    let stored = client
+        .get_event(&event_id)
+        .unwrap()
+        .tiers
+        .get(tier_id.clone())
+        .unwrap();
+    assert_eq!(stored.price, 7000);
+    assert_eq!(stored.tier_limit, 80);
+    assert_eq!(stored.current_sold, 10);
+
+    let result =
+        client.try_update_tier(&organizer, &event_id, &tier_id, &basic_tier(&env, 7000, 9));
+    assert_eq!(result, Err(Ok(EventRegistryError::TierLimitBelowSold)));
+
+    let result = client.try_update_tier(
+        &organizer,
+        &event_id,
+        &tier_id,
+        &basic_tier(&env, 7000, 101),
+    );
+    assert_eq!(
+        result,
+        Err(Ok(EventRegistryError::TierLimitExceedsMaxSupply))
+    );
+
+    let result = client.try_update_tier(
+        &organizer,
+        &event_id,
+        &String::from_str(&env, "vip"),
+        &basic_tier(&env, 7000, 10),
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::TierNotFound)));
+}
+
+#[test]
+fn test_retire_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, organizer) = setup_bulk_event(&env, 60, 100);
+    client.increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &10);
+
+    client.retire_tier(&organizer, &event_id, &tier_id);
+    let event = client.get_event(&event_id).unwrap();
+    assert!(event.tiers.get(tier_id.clone()).unwrap().is_retired);
+    assert_eq!(event.current_supply, 10);
+
+    let result = client.try_increment_inventory(&event_id, &tier_id);
+    assert_eq!(result, Err(Ok(EventRegistryError::TierRetired)));
+    let result = client.try_retire_tier(&organizer, &event_id, &tier_id);
+    assert_eq!(result, Err(Ok(EventRegistryError::TierRetired)));
+
+    // Refunds of retired tickets still release inventory
+    client.decrement_inventory(&event_id, &tier_id);
+    assert_eq!(client.get_event(&event_id).unwrap().current_supply, 9);
+
+    // The retired tier still counts towards max supply
+    let result = client.try_add_tier(
+        &organizer,
+        &event_id,
+        &String::from_str(&env, "vip"),
+        &basic_tier(&env, 9000, 41),
+    );
+    assert_eq!(
+        result,
+        Err(Ok(EventRegistryError::TierLimitExceedsMaxSupply))
+    );
+}
+
+fn legacy_event(env: &Env, event_id: &str, organizer: &Address) -> EventInfo {
+    let mut tiers = Map::new(env);
+    let mut tier = basic_tier(env, 1000, 50);
+    tier.current_sold = 20;
+    tiers.set(String::from_str(env, "general"), tier);
+    EventInfo {
+        event_id: String::from_str(env, event_id),
+        organizer_address: organizer.clone(),
+        payment_address: organizer.clone(),
+        platform_fee_percent: 300,
+        status: EventStatus::Published,
+        created_at: 10,
+        start_time: 0,
+        end_time: 1_000_000,
+        metadata_cid: String::from_str(
+            env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 100,
+        current_supply: 20,
+        max_per_buyer: 0,
+        tiers,
+    }
+}
+
+#[test]
+fn test_import_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let organizer = Address::generate(&env);
+    let event = legacy_event(&env, "legacy_1", &organizer);
+    client.import_events(&soroban_sdk::vec![&env, event.clone()]);
+
+    assert_eq!(client.get_event(&event.event_id), Some(event.clone()));
+    assert_eq!(client.get_organizer_events(&organizer).len(), 1);
+
+    // Imports never overwrite existing events
+    let result = client.try_import_events(&soroban_sdk::vec![&env, event]);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventAlreadyExists)));
+}
+
+#[test]
+fn test_import_events_validates_records() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+    let organizer = Address::generate(&env);
+
+    let mut bad_supply = legacy_event(&env, "bad_supply", &organizer);
+    bad_supply.current_supply = 0;
+    let mut bad_fee = legacy_event(&env, "bad_fee", &organizer);
+    bad_fee.platform_fee_percent = 10001;
+    let mut bad_cid = legacy_event(&env, "bad_cid", &organizer);
+    bad_cid.metadata_cid = String::from_str(&env, "Qm123");
+    let mut over_limit = legacy_event(&env, "over_limit", &organizer);
+    over_limit.max_supply = 10;
+
+    let cases = [
+        (bad_supply, EventRegistryError::SupplyMismatch),
+        (bad_fee, EventRegistryError::InvalidFeePercent),
+        (bad_cid, EventRegistryError::InvalidMetadataCid),
+        (over_limit, EventRegistryError::TierLimitExceedsMaxSupply),
+    ];
+    for (event, expected) in cases.iter() {
+        // A single bad record rejects the whole batch
+        let valid = legacy_event(&env, "valid", &organizer);
+        let result = client.try_import_events(&soroban_sdk::vec![&env, valid, event.clone()]);
+        assert_eq!(result, Err(Ok(*expected)));
+        assert!(!client.event_exists(&String::from_str(&env, "valid")));
+    }
+}
+
+#[test]
+fn test_event_status_transitions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, organizer) = setup_bulk_event(&env, 10, 0);
+
+    // Pausing stops sales, resuming restores them
+    client.update_event_status(&organizer, &event_id, &EventStatus::SalesPaused);
+    let result = client.try_increment_inventory(&event_id, &tier_id);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
+    client.update_event_status(&organizer, &event_id, &EventStatus::Published);
+    client.increment_inventory(&event_id, &tier_id);
+
+    let invalid = [EventStatus::Published, EventStatus::Draft];
+    for status in invalid.iter() {
+        let result = client.try_update_event_status(&organizer, &event_id, status);
+        assert_eq!(result, Err(Ok(EventRegistryError::InvalidStatusTransition)));
+    }
+
+    client.update_event_status(&organizer, &event_id, &EventStatus::Completed);
+    assert_eq!(
+        client.get_event(&event_id).unwrap().status,
+        EventStatus::Completed
+    );
+
+    // Completed is final
+    let result = client.try_update_event_status(&organizer, &event_id, &EventStatus::Cancelled);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStatusTransition)));
+}
+
+#[test]
+fn test_draft_event_can_be_published() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+    client.set_ticket_payment_contract(&Address::generate(&env));
+
+    let organizer = Address::generate(&env);
+    let event_id = String::from_str(&env, "draft");
+    let tier_id = String::from_str(&env, "general");
+    let mut tiers = Map::new(&env);
+    tiers.set(tier_id.clone(), basic_tier(&env, 1000, 10));
+    client.register_event(
+        &event_id,
+        &organizer,
+        &organizer,
+        &String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        &0,
+        &tiers,
+        &0,
+        &5000,
+        &false,
+    );
+    assert_eq!(
+        client.get_event(&event_id).unwrap().status,
+        EventStatus::Draft
+    );
+
+    let result = client.try_increment_inventory(&event_id, &tier_id);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
+    let result = client.try_update_event_status(&organizer, &event_id, &EventStatus::Completed);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStatusTransition)));
+
+    client.update_event_status(&organizer, &event_id, &EventStatus::Published);
+    client.increment_inventory(&event_id, &tier_id);
+}
+
+#[test]
+fn test_event_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, organizer) = setup_bulk_event(&env, 10, 0);
+    let manager = Address::generate(&env);
+    let scanner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    client.grant_role(&event_id, &manager, &EventRole::Manager);
+    client.grant_role(&event_id, &scanner, &EventRole::Scanner);
+    assert_eq!(
+        client.get_event_role(&event_id, &organizer),
+        Some(EventRole::Owner)
+    );
+    assert_eq!(
+        client.get_event_role(&event_id, &manager),
+        Some(EventRole::Manager)
+    );
+    assert_eq!(
+        client.get_event_role(&event_id, &scanner),
+        Some(EventRole::Scanner)
+    );
+    assert_eq!(client.get_event_role(&event_id, &stranger), None);
+
+    // Managers handle the event without the owner key
+    let new_cid = String::from_str(
+        &env,
+        "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
+    );
+    client.update_metadata(&manager, &event_id, &new_cid);
+    client.retire_tier(&manager, &event_id, &tier_id);
+    client.update_event_status(&manager, &event_id, &EventStatus::SalesPaused);
+    assert_eq!(client.get_event(&event_id).unwrap().metadata_cid, new_cid);
+
+    // Scanners and strangers cannot
+    for caller in [scanner.clone(), stranger].iter() {
+        let result = client.try_update_event_status(caller, &event_id, &EventStatus::Published);
+        assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
+    }
+
+    client.revoke_role(&event_id, &manager);
+    assert_eq!(client.get_event_role(&event_id, &manager), None);
+    let result = client.try_update_event_status(&manager, &event_id, &EventStatus::Published);
+    assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
+    let result = client.try_revoke_role(&event_id, &manager);
+    assert_eq!(result, Err(Ok(EventRegistryError::RoleNotFound)));
+
+    // Ownership is not a grantable role
+    let result = client.try_grant_role(&event_id, &scanner, &EventRole::Owner);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidRole)));
+    let result = client.try_grant_role(&event_id, &organizer, &EventRole::Manager);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidRole)));
+}
+
+#[test]
+fn test_event_ownership_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, _, organizer) = setup_bulk_event(&env, 10, 0);
+    let new_organizer = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+
+    let result = client.try_accept_event_transfer(&event_id);
+    assert_eq!(result, Err(Ok(EventRegistryError::TransferNotFound)));
+    let result = client.try_propose_event_transfer(&event_id, &organizer, &None);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidAddress)));
+
+    // A manager of the previous owner must not keep control after the transfer
+    let accomplice = Address::generate(&env);
+    client.grant_role(&event_id, &accomplice, &EventRole::Manager);
+    client.grant_role(&event_id, &new_organizer, &EventRole::Manager);
+    client.propose_event_transfer(&event_id, &new_organizer, &Some(new_wallet.clone()));
+    let pending = client.get_pending_event_transfer(&event_id).unwrap();
+    assert_eq!(pending.new_organizer, new_organizer);
+
+    // Nothing changes until the new organizer accepts
+    assert_eq!(
+        client.get_event(&event_id).unwrap().organizer_address,
+        organizer
+    );
+
+    client.accept_event_transfer(&event_id);
+    let event = client.get_event(&event_id).unwrap();
+    assert_eq!(event.organizer_address, new_organizer);
+    assert_eq!(event.payment_address, new_wallet);
+    assert_eq!(client.get_organizer_events(&organizer).len(), 0);
+    assert_eq!(
+        client.get_organizer_events(&new_organizer),
+        soroban_sdk::vec![&env, event_id.clone()]
+    );
+    assert_eq!(
+        client.get_event_role(&event_id, &new_organizer),
+        Some(EventRole::Owner)
+    );
+    assert_eq!(client.get_event_role(&event_id, &organizer), None);
+    assert_eq!(client.get_pending_event_transfer(&event_id), None);
+
+    assert_eq!(client.get_event_role(&event_id, &accomplice), None);
+    let result = client.try_update_metadata(
+        &accomplice,
+        &event_id,
+        &String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
+    let result = client.try_update_event_status(&accomplice, &event_id, &EventStatus::Cancelled);
+    assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
+}
+
+#[test]
+fn test_admin_transfer_and_platform_wallet() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env), &500);
+
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(EventRegistryError::NoPendingAdmin)));
+
+    let new_admin = Address::generate(&env);
+    client.propose_admin(&new_admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_admin(), admin);
+
+    client.accept_admin();
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+
+    let new_wallet = Address::generate(&env);
+    client.set_platform_wallet(&new_wallet);
+    assert_eq!(client.get_platform_wallet(), new_wallet);
+}
+
+#[test]
+fn test_pause_blocks_registration_and_sales() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, organizer) = setup_bulk_event(&env, 10, 0);
+    client.increment_inventory(&event_id, &tier_id);
+
+    client.pause(&1);
+    assert!(client.is_paused());
+
+    let result = client.try_register_event(
+        &String::from_str(&env, "paused_event"),
+        &organizer,
+        &organizer,
+        &String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        &0,
+        &Map::new(&env),
+        &0,
+        &1_000_000,
+        &true,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::ContractPaused)));
+    let result = client.try_increment_inventory(&event_id, &tier_id);
+    assert_eq!(result, Err(Ok(EventRegistryError::ContractPaused)));
+
+    // Refunds and queries keep working
+    client.decrement_inventory(&event_id, &tier_id);
+    assert_eq!(client.get_event(&event_id).unwrap().current_supply, 0);
+
+    client.unpause(&2);
+    assert!(!client.is_paused());
+    client.increment_inventory(&event_id, &tier_id);
+}
+
+#[test]
+fn test_fee_overrides() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, organizer) = setup_bulk_event(&env, 10, 0);
+    assert_eq!(
+        client
+            .get_event_payment_info(&event_id)
+            .platform_fee_percent,
+        500
+    );
+
+    // Organizer override applies to existing events and to new registrations
+    let organizer_target = FeeOverrideTarget::Organizer(organizer.clone());
+    client.set_fee_override(&organizer_target, &100, &Some(1000));
+    assert_eq!(
+        client
+            .get_event_payment_info(&event_id)
+            .platform_fee_percent,
+        100
+    );
+
+    let second_id = String::from_str(&env, "second_event");
+    let mut tiers = Map::new(&env);
+    tiers.set(tier_id.clone(), basic_tier(&env, 1000, 10));
+    client.register_event(
+        &second_id,
+        &organizer,
+        &organizer,
+        &String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        &0,
+        &tiers,
+        &0,
+        &1_000_000,
+        &true,
+    );
+    assert_eq!(
+        client
+            .get_event_payment_info(&second_id)
+            .platform_fee_percent,
+        100
+    );
+
+    // Event override takes precedence over the organizer override
+    let event_target = FeeOverrideTarget::Event(event_id.clone());
+    client.set_fee_override(&event_target, &0, &None);
+    assert_eq!(
+        client
+            .get_event_payment_info(&event_id)
+            .platform_fee_percent,
+        0
+    );
+    assert_eq!(client.get_active_fee_overrides().len(), 2);
+
+    // Expired organizer override drops out of the view and no longer applies
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let active = client.get_active_fee_overrides();
+    assert_eq!(active.len(), 1);
+    assert_eq!(active.get(0).unwrap().target, event_target);
+    assert_eq!(
+        client
+            .get_event_payment_info(&second_id)
+            .platform_fee_percent,
+        500
+    );
+
+    client.remove_fee_override(&event_target);
+    assert_eq!(
+        client
+            .get_event_payment_info(&event_id)
+            .platform_fee_percent,
+        500
+    );
+    assert_eq!(client.get_active_fee_overrides().len(), 0);
+
+    let result = client.try_remove_fee_override(&event_target);
+    assert_eq!(result, Err(Ok(EventRegistryError::FeeOverrideNotFound)));
+    let result = client.try_set_fee_override(&organizer_target, &10001, &None);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidFeePercent)));
+    let result = client.try_set_fee_override(&organizer_target, &100, &Some(1000));
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidEventTime)));
+}
+
+#[test]
+fn test_inventory_reservations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, _organizer) = setup_bulk_event(&env, 10, 0);
+    let backend = client.get_admin();
+    let ticket_payment = client.get_ticket_payment_contract();
+    let buyer = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.reserve_inventory(&backend, &event_id, &tier_id, &buyer, &4, &100);
+    client.reserve_inventory(&ticket_payment, &event_id, &tier_id, &other, &5, &200);
+    let availability = client.get_tier_availability(&event_id, &tier_id);
+    assert_eq!(availability.held, 9);
+    assert_eq!(availability.available, 1);
+
+    // Held units count against the tier limit for everyone else
+    let result =
+        client.try_increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &2);
+    assert_eq!(result, Err(Ok(EventRegistryError::TierSupplyExceeded)));
+    let result = client.try_reserve_inventory(
+        &backend,
+        &event_id,
+        &tier_id,
+        &Address::generate(&env),
+        &2,
+        &100,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::TierSupplyExceeded)));
+
+    // The buyer's payment consumes their own hold
+    client.increment_inventory_bulk(&event_id, &tier_id, &buyer, &3);
+    assert_eq!(
+        client
+            .get_reservation(&event_id, &tier_id, &buyer)
+            .unwrap()
+            .quantity,
+        1
+    );
+    let availability = client.get_tier_availability(&event_id, &tier_id);
+    assert_eq!(availability.sold, 3);
+    assert_eq!(availability.held, 6);
+    assert_eq!(availability.available, 1);
+
+    // Expired holds are released lazily
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert!(client
+        .get_reservation(&event_id, &tier_id, &buyer)
+        .is_none());
+    assert_eq!(
+        client.get_tier_availability(&event_id, &tier_id).available,
+        2
+    );
+    client.increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &2);
+
+    client.release_reservation(&other, &event_id, &tier_id, &other);
+    let availability = client.get_tier_availability(&event_id, &tier_id);
+    assert_eq!(availability.held, 0);
+    assert_eq!(availability.available, 5);
+
+    let result = client.try_release_reservation(&other, &event_id, &tier_id, &other);
+    assert_eq!(result, Err(Ok(EventRegistryError::ReservationNotFound)));
+    let result = client.try_reserve_inventory(&buyer, &event_id, &tier_id, &buyer, &1, &500);
+    assert_eq!(result, Err(Ok(EventRegistryError::UnauthorizedCaller)));
+    let result = client.try_reserve_inventory(&backend, &event_id, &tier_id, &buyer, &1, &100);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidEventTime)));
+}
+
+#[test]
+fn test_reservations_count_against_max_supply() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, _organizer) = setup_bulk_event(&env, 6, 6);
+    let backend = client.get_admin();
+    let buyer = Address::generate(&env);
+
+    client.reserve_inventory(&backend, &event_id, &tier_id, &buyer, &5, &100);
+    assert_eq!(
+        client.get_tier_availability(&event_id, &tier_id).available,
+        1
+    );
+
+    let result =
+        client.try_increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &2);
+    assert_eq!(result, Err(Ok(EventRegistryError::MaxSupplyExceeded)));
+
+    // Re-reserving replaces the previous hold
+    client.reserve_inventory(&backend, &event_id, &tier_id, &buyer, &2, &100);
+    assert_eq!(
+        client.get_tier_availability(&event_id, &tier_id).available,
+        4
+    );
+    client.increment_inventory_bulk(&event_id, &tier_id, &buyer, &6);
+    assert!(client
+        .get_reservation(&event_id, &tier_id, &buyer)
+        .is_none());
+    assert_eq!(client.get_event(&event_id).unwrap().current_supply, 6);
+}
+
+#[test]
+fn test_waitlist_offers_freed_capacity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, _organizer) = setup_bulk_event(&env, 4, 0);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    // The tier must be sold out to join
+    let result = client.try_join_waitlist(&event_id, &tier_id, &first, &2);
+    assert_eq!(result, Err(Ok(EventRegistryError::TicketsAvailable)));
+
+    client.increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &4);
+    client.join_waitlist(&event_id, &tier_id, &first, &2);
+    client.join_waitlist(&event_id, &tier_id, &second, &1);
+    let result = client.try_join_waitlist(&event_id, &tier_id, &first, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::AlreadyOnWaitlist)));
+    assert_eq!(client.get_waitlist(&event_id, &tier_id).len(), 2);
+
+    // One refunded ticket is not enough for the first buyer, and the queue is not skipped
+    client.decrement_inventory(&event_id, &tier_id);
+    assert!(client
+        .get_reservation(&event_id, &tier_id, &second)
+        .is_none());
+    assert_eq!(client.get_waitlist(&event_id, &tier_id).len(), 2);
+
+    // Freed capacity becomes a time-limited hold for the first buyer
+    client.decrement_inventory(&event_id, &tier_id);
+    let offer = client.get_reservation(&event_id, &tier_id, &first).unwrap();
+    assert_eq!(offer.quantity, 2);
+    assert_eq!(offer.expires_at, WAITLIST_OFFER_DURATION);
+    let waitlist = client.get_waitlist(&event_id, &tier_id);
+    assert_eq!(waitlist.len(), 1);
+    assert_eq!(waitlist.get(0).unwrap().buyer, second);
+
+    let result =
+        client.try_increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::TierSupplyExceeded)));
+    client.increment_inventory_bulk(&event_id, &tier_id, &first, &2);
+    assert!(client
+        .get_reservation(&event_id, &tier_id, &first)
+        .is_none());
+
+    client.leave_waitlist(&event_id, &tier_id, &second);
+    assert_eq!(client.get_waitlist(&event_id, &tier_id).len(), 0);
+    let result = client.try_leave_waitlist(&event_id, &tier_id, &second);
+    assert_eq!(result, Err(Ok(EventRegistryError::NotOnWaitlist)));
+}
+
+#[test]
+fn test_promo_codes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, organizer) = setup_bulk_event(&env, 10, 0);
+    let code = soroban_sdk::Bytes::from_slice(&env, b"EARLY25");
+    let code_hash: soroban_sdk::BytesN<32> = env.crypto().sha256(&code).into();
+    let mut tiers = Vec::new(&env);
+    tiers.push_back(tier_id.clone());
+
+    let result = client.try_add_promo_code(
+        &organizer,
+        &event_id,
+        &code_hash,
+        &Discount::Fixed(5000000),
+        &tiers,
+        &2,
+        &1000,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidDiscount)));
+    let result = client.try_add_promo_code(
+        &Address::generate(&env),
+        &event_id,
+        &code_hash,
+        &Discount::Percentage(2500),
+        &tiers,
+        &2,
+        &1000,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
+
+    client.add_promo_code(
+        &organizer,
+        &event_id,
+        &code_hash,
+        &Discount::Percentage(2500),
+        &tiers,
+        &2,
+        &1000,
+    );
+    let result = client.try_add_promo_code(
+        &organizer,
+        &event_id,
+        &code_hash,
+        &Discount::Percentage(1000),
+        &Vec::new(&env),
+        &0,
+        &0,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::PromoCodeAlreadyExists)));
+
+    // Only the hash is stored; redemption looks the code up by its text
+    let promo = client.redeem_promo_code(&event_id, &tier_id, &code, &1);
+    assert_eq!(promo.code_hash, code_hash);
+    assert_eq!(promo.redemptions, 1);
+
+    let result = client.try_redeem_promo_code(&event_id, &String::from_str(&env, "vip"), &code, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::PromoCodeNotApplicable)));
+    let result = client.try_redeem_promo_code(
+        &event_id,
+        &tier_id,
+        &soroban_sdk::Bytes::from_slice(&env, b"early25"),
+        &1,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::PromoCodeNotFound)));
+
+    // Each ticket is a redemption, so two tickets do not fit in the one left
+    let result = client.try_redeem_promo_code(&event_id, &tier_id, &code, &2);
+    assert_eq!(result, Err(Ok(EventRegistryError::PromoCodeExhausted)));
+    client.redeem_promo_code(&event_id, &tier_id, &code, &1);
+    let result = client.try_redeem_promo_code(&event_id, &tier_id, &code, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::PromoCodeExhausted)));
+
+    // Refunded tickets give their redemptions back
+    client.credit_promo_code(&event_id, &code_hash, &1);
+    let promo = client.get_promo_code(&event_id, &code_hash).unwrap();
+    assert_eq!(promo.redemptions, 1);
+    client.redeem_promo_code(&event_id, &tier_id, &code, &1);
+
+    client.remove_promo_code(&organizer, &event_id, &code_hash);
+    client.add_promo_code(
+        &organizer,
+        &event_id,
+        &code_hash,
+        &Discount::Fixed(1000000),
+        &Vec::new(&env),
+        &0,
+        &1000,
+    );
+
+    // Tier changes cannot bring any price of a covered tier down to the fixed discount
+    let mut tier = basic_tier(&env, 5000000, 10);
+    let result = client.try_update_tier(
+        &organizer,
+        &event_id,
+        &tier_id,
+        &basic_tier(&env, 1000000, 10),
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidDiscount)));
+    tier.price_schedule.push_back(PriceStep {
+        trigger: PriceTrigger::Sold(5),
+        price: 900000,
+    });
+    let result = client.try_update_tier(&organizer, &event_id, &tier_id, &tier);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidDiscount)));
+    let vip = String::from_str(&env, "vip");
+    let result = client.try_add_tier(&organizer, &event_id, &vip, &tier);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidDiscount)));
+
+    // A new code is checked against the price steps too
+    client.remove_promo_code(&organizer, &event_id, &code_hash);
+    client.add_tier(&organizer, &event_id, &vip, &tier);
+    let result = client.try_add_promo_code(
+        &organizer,
+        &event_id,
+        &code_hash,
+        &Discount::Fixed(1000000),
+        &Vec::new(&env),
+        &0,
+        &1000,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidDiscount)));
+    client.add_promo_code(
+        &organizer,
+        &event_id,
+        &code_hash,
+        &Discount::Fixed(800000),
+        &Vec::new(&env),
+        &0,
+        &1000,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let result = client.try_redeem_promo_code(&event_id, &tier_id, &code, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::PromoCodeExpired)));
+}
+
+#[test]
+fn test_set_max_per_buyer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, organizer) = setup_bulk_event(&env, 10, 0);
+
+    client.set_max_per_buyer(&organizer, &event_id, &None, &4);
+    client.set_max_per_buyer(&organizer, &event_id, &Some(tier_id.clone()), &2);
+    let event = client.get_event(&event_id).unwrap();
+    assert_eq!(event.max_per_buyer, 4);
+    assert_eq!(event.tiers.get(tier_id).unwrap().max_per_buyer, 2);
+
+    let result = client.try_set_max_per_buyer(
+        &organizer,
+        &event_id,
+        &Some(String::from_str(&env, "missing")),
+        &2,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::TierNotFound)));
+    let result = client.try_set_max_per_buyer(&Address::generate(&env), &event_id, &None, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
+}
+
+#[test]
+fn test_tier_access_rules_and_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, organizer) = setup_bulk_event(&env, 10, 0);
+    let member = Address::generate(&env);
+
+    client.set_access_rule(&organizer, &event_id, &tier_id, &AccessRule::Allowlist);
+    let tier = client
+        .get_event(&event_id)
+        .unwrap()
+        .tiers
+        .get(tier_id.clone());
+    assert_eq!(tier.unwrap().access_rule, AccessRule::Allowlist);
+
+    let mut accounts = Vec::new(&env);
+    accounts.push_back(member.clone());
+    client.set_tier_allowlist(&organizer, &event_id, &tier_id, &accounts, &true);
+    assert!(client.is_allowlisted(&event_id, &tier_id, &member));
+    client.set_tier_allowlist(&organizer, &event_id, &tier_id, &accounts, &false);
+    assert!(!client.is_allowlisted(&event_id, &tier_id, &member));
+
+    let result = client.try_set_access_rule(
+        &organizer,
+        &event_id,
+        &tier_id,
+        &AccessRule::TokenHolder(Address::generate(&env), 0),
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidAccessRule)));
+    let result = client.try_set_tier_allowlist(
+        &Address::generate(&env),
+        &event_id,
+        &tier_id,
+        &accounts,
+        &true,
+    );
+    assert_eq!(result, Err(Ok(EventRegistryError::Unauthorized)));
+}
+
+#[test]
+fn test_waitlist_quantity_bounds_and_lapsed_offers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, event_id, tier_id, organizer) = setup_bulk_event(&env, 4, 0);
+    client.increment_inventory_bulk(&event_id, &tier_id, &Address::generate(&env), &4);
+
+    // Entries that could never be filled are rejected
+    let squatter = Address::generate(&env);
+    let result = client.try_join_waitlist(&event_id, &tier_id, &squatter, &u32::MAX);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidQuantity)));
+    client.set_max_per_buyer(&organizer, &event_id, &Some(tier_id.clone()), &2);
+    let result = client.try_join_waitlist(&event_id, &tier_id, &squatter, &3);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidQuantity)));
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    client.join_waitlist(&event_id, &tier_id, &first, &2);
+    client.join_waitlist(&event_id, &tier_id, &second, &2);
+    client.decrement_inventory_bulk(&event_id, &tier_id, &2);
+    assert!(client
+        .get_reservation(&event_id, &tier_id, &first)
+        .is_some());
+
+    // The first buyer lets the offer lapse; the next write passes it on
+    env.ledger()
+        .with_mut(|li| li.timestamp = WAITLIST_OFFER_DURATION);
+    client.join_waitlist(&event_id, &tier_id, &Address::generate(&env), &1);
+    let offer = client
+        .get_reservation(&event_id, &tier_id, &second)
+        .unwrap();
+    assert_eq!(offer.quantity, 2);
+    assert_eq!(offer.expires_at, 2 * WAITLIST_OFFER_DURATION);
+    assert_eq!(client.get_waitlist(&event_id, &tier_id).len(), 1);
+}
@@ -1,4 +1,49 @@
-use soroban_sdk::{contracttype, Address, Map, String};
+use soroban_sdk::{contracttype, Address, BytesN, Map, String, Vec};
+
+/// One step of a tier's refund schedule
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundWindow {
+    /// Last ledger timestamp (inclusive) at which this step applies
+    pub deadline: u64,
+    /// Share of the ticket price refunded in basis points (10000 = 100%)
+    pub refund_bps: u32,
+}
+
+/// What moves a tier onto a price step
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PriceTrigger {
+    /// Applies from this ledger timestamp on
+    Time(u64),
+    /// Applies once this many tickets of the tier have been sold
+    Sold(i128),
+}
+
+/// One step of a tier's dynamic price schedule
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceStep {
+    /// Condition under which this step applies
+    pub trigger: PriceTrigger,
+    /// Price in stroops once the step applies
+    pub price: i128,
+}
+
+/// Who may buy a ticket tier
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AccessRule {
+    /// Anyone can buy
+    Open,
+    /// Only addresses added with `set_tier_allowlist`
+    Allowlist,
+    /// Only addresses in a Merkle tree with this root. Leaves are the SHA-256 of the
+    /// buyer's XDR-encoded address and pairs are hashed in sorted order.
+    MerkleRoot(BytesN<32>),
+    /// Only holders of at least `min_balance` of `token`
+    TokenHolder(Address, i128),
+}
 
 /// Represents a ticket tier with its own pricing and supply
 #[contracttype]
@@ -6,7 +51,7 @@ use soroban_sdk::{contracttype, Address, Map, String};
 pub struct TicketTier {
     /// Name of the tier (e.g., "General", "VIP", "Reserved")
     pub name: String,
-    /// Price for this tier in stroops
+    /// Base price for this tier in stroops, charged until a price step applies
     pub price: i128,
     /// Maximum tickets available for this tier
     pub tier_limit: i128,
@@ -14,6 +59,53 @@ pub struct TicketTier {
     pub current_sold: i128,
     /// Indicates whether tickets in this tier can be refunded by the buyer
     pub is_refundable: bool,
+    /// Time-based refund steps ordered by deadline. When empty, refundable tickets
+    /// are refunded in full at any time. After the last deadline nothing is refunded.
+    pub refund_schedule: Vec<RefundWindow>,
+    /// Maximum resale price as a share of `price` in basis points (0 = resale disabled)
+    pub max_resale_bps: u32,
+    /// Royalty paid to the organizer on each resale in basis points
+    pub royalty_bps: u32,
+    /// Timestamp at which sales for this tier open (0 = open immediately)
+    pub sale_start: u64,
+    /// Timestamp at which sales for this tier close (0 = open until the event starts)
+    pub sale_end: u64,
+    /// Price steps in order; the last step whose trigger is reached sets the current price
+    pub price_schedule: Vec<PriceStep>,
+    /// Retired tiers can no longer be bought but still count towards supply totals
+    pub is_retired: bool,
+    /// Maximum tickets of this tier a single buyer may purchase (0 = no limit)
+    pub max_per_buyer: u32,
+    /// Who may buy this tier
+    pub access_rule: AccessRule,
+}
+
+/// Lifecycle state of an event
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventStatus {
+    /// Being prepared; not on sale yet
+    Draft,
+    /// On sale
+    Published,
+    /// Temporarily not selling tickets
+    SalesPaused,
+    /// Cancelled; every ticket is refundable in full
+    Cancelled,
+    /// The event took place
+    Completed,
+}
+
+/// Role an address holds on a single event
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventRole {
+    /// The organizer; manages the event and its roles
+    Owner,
+    /// Can update metadata, tiers and status and withdraw funds
+    Manager,
+    /// Can only check tickets in
+    Scanner,
 }
 
 /// Represents information about an event in the registry.
@@ -26,18 +118,24 @@ pub struct EventInfo {
     pub organizer_address: Address,
     /// The address where payments for this event should be routed
     pub payment_address: Address,
-    /// The percentage fee taken by the platform (e.g., 5 for 5%)
+    /// The percentage fee taken by the platform (e.g., 5 for 5%), before fee overrides
     pub platform_fee_percent: u32,
-    /// Whether the event is currently active and accepting payments
-    pub is_active: bool,
+    /// Lifecycle state; tickets can only be bought while `Published`
+    pub status: EventStatus,
     /// Timestamp when the event was created
     pub created_at: u64,
+    /// Timestamp when the event starts
+    pub start_time: u64,
+    /// Timestamp when the event ends; escrowed funds unlock after this plus the hold period
+    pub end_time: u64,
     /// IPFS Content Identifier storing rich metadata details
     pub metadata_cid: String,
     /// Maximum number of tickets available for this event (0 = unlimited)
     pub max_supply: i128,
     /// Current number of tickets that have been successfully purchased
     pub current_supply: i128,
+    /// Maximum tickets a single buyer may purchase across all tiers (0 = no limit)
+    pub max_per_buyer: u32,
     /// Map of tier_id to TicketTier for multi-tiered pricing
     pub tiers: Map<String, TicketTier>,
 }
@@ -54,6 +152,108 @@ pub struct PaymentInfo {
     pub tiers: Map<String, TicketTier>,
 }
 
+/// Ownership transfer waiting for the new organizer to accept
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingEventTransfer {
+    /// The organizer who will own the event
+    pub new_organizer: Address,
+    /// New payment address, if it should change with the owner
+    pub new_payment_address: Option<Address>,
+    /// Timestamp when the transfer was proposed
+    pub proposed_at: u64,
+}
+
+/// What a platform fee override applies to
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeeOverrideTarget {
+    /// Every event of an organizer (e.g. a Pro plan subscriber)
+    Organizer(Address),
+    /// A single event; takes precedence over an organizer override
+    Event(String),
+}
+
+/// Platform fee applied instead of the global fee
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeOverride {
+    /// Organizer or event the override applies to
+    pub target: FeeOverrideTarget,
+    /// Fee in basis points (10000 = 100%)
+    pub fee_percent: u32,
+    /// Timestamp after which the override no longer applies (None = never expires)
+    pub expires_at: Option<u64>,
+}
+
+/// Units of a tier held for a buyer between checkout and payment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reservation {
+    /// Tier the units are held in
+    pub tier_id: String,
+    /// Buyer the units are held for
+    pub buyer: Address,
+    /// Number of units held
+    pub quantity: u32,
+    /// Timestamp at which the hold lapses
+    pub expires_at: u64,
+}
+
+/// Sold, held and still purchasable units of a tier
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierAvailability {
+    /// Maximum tickets available for the tier
+    pub tier_limit: i128,
+    /// Tickets of the tier sold so far
+    pub sold: i128,
+    /// Units under unexpired reservations
+    pub held: i128,
+    /// Units that can still be reserved or bought, after tier and event limits
+    pub available: i128,
+}
+
+/// A buyer waiting for capacity in a sold-out tier
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaitlistEntry {
+    /// Buyer waiting for the tier
+    pub buyer: Address,
+    /// Units the buyer wants; offers are only made for the full quantity
+    pub quantity: u32,
+    /// Timestamp when the buyer joined the waitlist
+    pub joined_at: u64,
+}
+
+/// Discount granted by a promo code, applied to each ticket's current price
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Discount {
+    /// Share of the price taken off, in basis points
+    Percentage(u32),
+    /// Fixed amount taken off, in the payment token's base units
+    Fixed(i128),
+}
+
+/// A promo code, stored under the SHA-256 hash of its text
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromoCode {
+    /// SHA-256 hash of the code text
+    pub code_hash: BytesN<32>,
+    /// Discount applied to each ticket bought with the code
+    pub discount: Discount,
+    /// Tiers the code applies to (empty = every tier)
+    pub tiers: Vec<String>,
+    /// Maximum number of tickets the code can discount (0 = unlimited)
+    pub max_redemptions: u32,
+    /// Tickets discounted so far, less refunded ones
+    pub redemptions: u32,
+    /// Timestamp from which the code is no longer accepted (0 = never expires)
+    pub expires_at: u64,
+}
+
 /// Storage keys for the Event Registry contract.
 #[contracttype]
 pub enum DataKey {
@@ -71,4 +271,28 @@ pub enum DataKey {
     OrganizerEvents(Address),
     /// The authorized TicketPayment contract address for inventory updates
     TicketPaymentContract,
+    /// Role granted to an address on an event (Persistent)
+    EventRole(String, Address),
+    /// Addresses holding a role on an event, so roles can be cleared on transfer (Persistent)
+    EventRoleHolders(String),
+    /// Ownership transfer proposed for an event (Persistent)
+    PendingEventTransfer(String),
+    /// Administrator proposed by the current one, waiting to accept
+    PendingAdmin,
+    /// Emergency pause flag
+    Paused,
+    /// Platform fee override for an organizer or event (Persistent)
+    FeeOverride(FeeOverrideTarget),
+    /// List of every target with a fee override (Persistent)
+    FeeOverrideTargets,
+    /// Inventory holds of an event, expired ones pruned on write (Persistent)
+    Reservations(String),
+    /// Buyers waiting for a tier, in order: (event_id, tier_id) (Persistent)
+    Waitlist(String, String),
+    /// Promo code of an event: (event_id, code_hash) (Persistent)
+    PromoCode(String, BytesN<32>),
+    /// Hashes of every promo code of an event (Persistent)
+    EventPromoCodes(String),
+    /// Allowlisted buyer of a gated tier: (event_id, tier_id, account) (Persistent)
+    TierAllowlist(String, String, Address),
 }
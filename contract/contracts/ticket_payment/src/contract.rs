@@ -1,28 +1,43 @@
 use crate::storage::{
-    add_token_to_whitelist, get_admin, get_event_balance, get_event_registry, get_payment,
-    get_platform_wallet, is_initialized, is_token_whitelisted, remove_token_from_whitelist,
-    set_admin, set_event_registry, set_initialized, set_platform_wallet, set_usdc_token,
-    store_payment, update_event_balance, update_payment_status,
+    add_owner_ticket, add_token_to_whitelist, get_admin, get_buyer_purchases, get_event_balance,
+    get_event_payments, get_event_registry, get_event_tokens, get_listing, get_owner_tickets,
+    get_payment, get_payment_tickets, get_pending_admin, get_platform_wallet, get_refund_cursor,
+    get_release_hold_period, get_ticket, is_initialized, is_paused, is_token_whitelisted,
+    is_trusted_organizer, next_ticket_id, remove_listing, remove_owner_ticket,
+    remove_pending_admin, remove_token_from_whitelist, set_admin, set_buyer_purchases,
+    set_event_registry, set_initialized, set_listing, set_paused, set_payment_tickets,
+    set_pending_admin, set_platform_wallet, set_refund_cursor, set_release_hold_period,
+    set_trusted_organizer, set_usdc_token, store_payment, store_ticket, update_event_balance,
+    update_payment_status,
+};
+use crate::types::{
+    BuyerPurchases, Listing, Payment, PaymentStatus, RefundBatchResult, Ticket, TicketStatus,
 };
-use crate::types::{Payment, PaymentStatus};
 use crate::{
     error::TicketPaymentError,
     events::{
-        AgoraEvent, ContractUpgraded, InitializationEvent, PaymentProcessedEvent,
-        PaymentStatusChangedEvent,
+        AdminChangedEvent, AdminProposedEvent, AgoraEvent, CancellationRefundProgressEvent,
+        ContractUpgraded, HoldPeriodUpdatedEvent, InitializationEvent, ListingCancelledEvent,
+        OrganizerTrustUpdatedEvent, PauseStatusEvent, PaymentProcessedEvent,
+        PaymentStatusChangedEvent, PlatformWalletUpdatedEvent, RefundProcessedEvent,
+        TicketCheckedInEvent, TicketListedEvent, TicketResoldEvent, TicketTransferredEvent,
+        TicketsIssuedEvent,
     },
 };
-use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env, String};
+use soroban_sdk::{
+    contract, contractimpl, token, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, String, Vec,
+};
 
 // Event Registry interface
 pub mod event_registry {
-    use soroban_sdk::{contractclient, Address, Env, String};
+    use soroban_sdk::{contractclient, Address, Bytes, BytesN, Env, String};
 
     #[soroban_sdk::contracttype]
     #[derive(Clone, Debug, Eq, PartialEq)]
     pub struct PaymentInfo {
         pub payment_address: Address,
         pub platform_fee_percent: u32,
+        pub tiers: soroban_sdk::Map<String, TicketTier>,
     }
 
     #[soroban_sdk::contracttype]
@@ -36,8 +51,76 @@ pub mod event_registry {
     pub trait EventRegistryInterface {
         fn get_event_payment_info(env: Env, event_id: String) -> PaymentInfo;
         fn get_event(env: Env, event_id: String) -> Option<EventInfo>;
+        fn get_current_price(env: Env, event_id: String, tier_id: String) -> i128;
+        fn get_event_role(env: Env, event_id: String, account: Address) -> Option<EventRole>;
+        fn is_paused(env: Env) -> bool;
         fn increment_inventory(env: Env, event_id: String, tier_id: String);
+        fn increment_inventory_bulk(
+            env: Env,
+            event_id: String,
+            tier_id: String,
+            buyer: Address,
+            quantity: u32,
+        );
         fn decrement_inventory(env: Env, event_id: String, tier_id: String);
+        fn decrement_inventory_bulk(env: Env, event_id: String, tier_id: String, quantity: u32);
+        fn is_allowlisted(env: Env, event_id: String, tier_id: String, account: Address) -> bool;
+        fn redeem_promo_code(
+            env: Env,
+            event_id: String,
+            tier_id: String,
+            code: Bytes,
+            quantity: u32,
+        ) -> PromoCode;
+        fn credit_promo_code(env: Env, event_id: String, code_hash: BytesN<32>, quantity: u32);
+    }
+
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum AccessRule {
+        Open,
+        Allowlist,
+        MerkleRoot(BytesN<32>),
+        TokenHolder(Address, i128),
+    }
+
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum Discount {
+        Percentage(u32),
+        Fixed(i128),
+    }
+
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct PromoCode {
+        pub code_hash: BytesN<32>,
+        pub discount: Discount,
+        pub tiers: soroban_sdk::Vec<String>,
+        pub max_redemptions: u32,
+        pub redemptions: u32,
+        pub expires_at: u64,
+    }
+
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct RefundWindow {
+        pub deadline: u64,
+        pub refund_bps: u32,
+    }
+
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum PriceTrigger {
+        Time(u64),
+        Sold(i128),
+    }
+
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct PriceStep {
+        pub trigger: PriceTrigger,
+        pub price: i128,
     }
 
     #[soroban_sdk::contracttype]
@@ -48,6 +131,33 @@ pub mod event_registry {
         pub tier_limit: i128,
         pub current_sold: i128,
         pub is_refundable: bool,
+        pub refund_schedule: soroban_sdk::Vec<RefundWindow>,
+        pub max_resale_bps: u32,
+        pub royalty_bps: u32,
+        pub sale_start: u64,
+        pub sale_end: u64,
+        pub price_schedule: soroban_sdk::Vec<PriceStep>,
+        pub is_retired: bool,
+        pub max_per_buyer: u32,
+        pub access_rule: AccessRule,
+    }
+
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum EventRole {
+        Owner,
+        Manager,
+        Scanner,
+    }
+
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum EventStatus {
+        Draft,
+        Published,
+        SalesPaused,
+        Cancelled,
+        Completed,
     }
 
     #[soroban_sdk::contracttype]
@@ -57,15 +167,25 @@ pub mod event_registry {
         pub organizer_address: Address,
         pub payment_address: Address,
         pub platform_fee_percent: u32,
-        pub is_active: bool,
+        pub status: EventStatus,
         pub created_at: u64,
+        pub start_time: u64,
+        pub end_time: u64,
         pub metadata_cid: String,
         pub max_supply: i128,
         pub current_supply: i128,
+        pub max_per_buyer: u32,
         pub tiers: soroban_sdk::Map<String, TicketTier>,
     }
 }
 
+/// Refund share in basis points for a full refund.
+const FULL_REFUND_BPS: u32 = 10000;
+
+/// Upper bound on payments visited by one `refund_cancelled_event` call, keeping each
+/// batch within Soroban resource limits.
+pub const MAX_REFUND_BATCH_SIZE: u32 = 25;
+
 #[contract]
 pub struct TicketPaymentContract;
 
@@ -131,6 +251,101 @@ impl TicketPaymentContract {
         );
     }
 
+    /// Proposes a new administrator. Only callable by the current administrator.
+    /// The change takes effect once the proposed address calls `accept_admin`;
+    /// a new proposal replaces any pending one.
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), TicketPaymentError> {
+        let admin = get_admin(&env).ok_or(TicketPaymentError::NotInitialized)?;
+        admin.require_auth();
+
+        validate_address(&env, &new_admin)?;
+        set_pending_admin(&env, &new_admin);
+
+        env.events().publish(
+            (AgoraEvent::AdminProposed,),
+            AdminProposedEvent {
+                current_admin: admin,
+                proposed_admin: new_admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Completes an administrator change. Only callable by the proposed administrator.
+    pub fn accept_admin(env: Env) -> Result<(), TicketPaymentError> {
+        let previous_admin = get_admin(&env).ok_or(TicketPaymentError::NotInitialized)?;
+        let new_admin = get_pending_admin(&env).ok_or(TicketPaymentError::NoPendingAdmin)?;
+        new_admin.require_auth();
+
+        set_admin(&env, &new_admin);
+        remove_pending_admin(&env);
+
+        env.events().publish(
+            (AgoraEvent::AdminChanged,),
+            AdminChangedEvent {
+                previous_admin,
+                new_admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Pauses ticket sales, resale purchases and withdrawals in an emergency. Refunds
+    /// and queries keep working. Only callable by the administrator.
+    pub fn pause(env: Env, reason: u32) -> Result<(), TicketPaymentError> {
+        set_pause_state(&env, true, reason)
+    }
+
+    /// Lifts an emergency pause. Only callable by the administrator.
+    pub fn unpause(env: Env, reason: u32) -> Result<(), TicketPaymentError> {
+        set_pause_state(&env, false, reason)
+    }
+
+    /// Returns whether the contract is paused.
+    pub fn is_paused(env: Env) -> bool {
+        is_paused(&env)
+    }
+
+    /// Returns the current administrator address.
+    pub fn get_admin(env: Env) -> Option<Address> {
+        get_admin(&env)
+    }
+
+    /// Returns the administrator waiting to accept the role, if any.
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        get_pending_admin(&env)
+    }
+
+    /// Updates the wallet that receives platform fees. Only callable by the administrator.
+    pub fn set_platform_wallet(env: Env, new_wallet: Address) -> Result<(), TicketPaymentError> {
+        let admin = get_admin(&env).ok_or(TicketPaymentError::NotInitialized)?;
+        admin.require_auth();
+
+        validate_address(&env, &new_wallet)?;
+        let old_wallet = get_platform_wallet(&env);
+        set_platform_wallet(&env, new_wallet.clone());
+
+        env.events().publish(
+            (AgoraEvent::PlatformWalletUpdated,),
+            PlatformWalletUpdatedEvent {
+                old_wallet,
+                new_wallet,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the wallet that receives platform fees.
+    pub fn get_platform_wallet(env: Env) -> Address {
+        get_platform_wallet(&env)
+    }
+
     pub fn add_token(env: Env, token: Address) {
         let admin = get_admin(&env).expect("Admin not set");
         admin.require_auth();
@@ -147,7 +362,13 @@ impl TicketPaymentContract {
         is_token_whitelisted(&env, &token)
     }
 
-    /// Processes a payment for an event ticket.
+    /// Processes a payment for `quantity` tickets of a single tier.
+    ///
+    /// The `amount` must match the tier's current price multiplied by `quantity` exactly;
+    /// any difference is rejected with `Underpayment` or `Overpayment`. An optional
+    /// `promo_code` is checked and redeemed by the registry, and its discount is taken
+    /// off each ticket's price. Gated tiers reject buyers that fail the tier's access
+    /// rule; `access_proof` carries the Merkle proof for Merkle-gated tiers.
     pub fn process_payment(
         env: Env,
         payment_id: String,
@@ -156,20 +377,32 @@ impl TicketPaymentContract {
         buyer_address: Address,
         token_address: Address,
         amount: i128,
+        quantity: u32,
+        promo_code: Option<Bytes>,
+        access_proof: Option<Vec<BytesN<32>>>,
     ) -> Result<String, TicketPaymentError> {
         if !is_initialized(&env) {
             panic!("Contract not initialized");
         }
+        ensure_not_paused(&env)?;
         buyer_address.require_auth();
 
         if amount <= 0 {
             panic!("Amount must be positive");
         }
 
+        if quantity == 0 {
+            return Err(TicketPaymentError::InvalidQuantity);
+        }
+
         if !is_token_whitelisted(&env, &token_address) {
             return Err(TicketPaymentError::TokenNotWhitelisted);
         }
 
+        if get_payment(&env, payment_id.clone()).is_some() {
+            return Err(TicketPaymentError::PaymentAlreadyExists);
+        }
+
         // 1. Query Event Registry for event info and check inventory
         let event_registry_addr = get_event_registry(&env);
         let registry_client = event_registry::Client::new(&env, &event_registry_addr);
@@ -180,17 +413,90 @@ impl TicketPaymentContract {
             _ => return Err(TicketPaymentError::EventNotFound),
         };
 
-        if !event_info.is_active {
+        if event_info.status != event_registry::EventStatus::Published {
             return Err(TicketPaymentError::EventInactive);
         }
 
-        // Check if tickets are available (max_supply of 0 means unlimited)
-        if event_info.max_supply > 0 && event_info.current_supply >= event_info.max_supply {
+        // A paused registry cannot record the sale, so refuse before taking payment
+        if registry_client.is_paused() {
+            return Err(TicketPaymentError::ContractPaused);
+        }
+
+        // Check if tickets are available for the whole batch (max_supply of 0 means unlimited)
+        if event_info.max_supply > 0
+            && event_info.current_supply + quantity as i128 > event_info.max_supply
+        {
             return Err(TicketPaymentError::MaxSupplyExceeded);
         }
 
-        // 2. Calculate platform fee (platform_fee_percent is in bps, 10000 = 100%)
-        let platform_fee = (amount * event_info.platform_fee_percent as i128) / 10000;
+        // The amount due is set by the tier price, not by the caller
+        let tier = event_info
+            .tiers
+            .get(ticket_tier_id.clone())
+            .ok_or(TicketPaymentError::TierNotFound)?;
+
+        if tier.is_retired {
+            return Err(TicketPaymentError::TierRetired);
+        }
+        check_sales_window(&env, &event_info, &tier)?;
+        check_price_steps(&tier, quantity)?;
+        check_tier_access(
+            &env,
+            &registry_client,
+            &event_id,
+            &ticket_tier_id,
+            &tier,
+            &buyer_address,
+            access_proof,
+        )?;
+
+        let mut purchases = get_buyer_purchases(&env, &event_id, &buyer_address);
+        add_purchases(
+            &event_info,
+            &tier,
+            &ticket_tier_id,
+            &mut purchases,
+            quantity,
+        )?;
+
+        // Early-bird and demand-based schedules are resolved by the registry
+        let unit_price = match registry_client.try_get_current_price(&event_id, &ticket_tier_id) {
+            Ok(Ok(price)) => price,
+            _ => return Err(TicketPaymentError::TierNotFound),
+        };
+
+        // The registry validates the code and counts one redemption per ticket
+        let mut promo_code_hash = None;
+        let charged_price = match promo_code {
+            Some(code) => {
+                let promo = match registry_client.try_redeem_promo_code(
+                    &event_id,
+                    &ticket_tier_id,
+                    &code,
+                    &quantity,
+                ) {
+                    Ok(Ok(promo)) => promo,
+                    _ => return Err(TicketPaymentError::InvalidPromoCode),
+                };
+                promo_code_hash = Some(promo.code_hash);
+                discounted_price(unit_price, &promo.discount)
+            }
+            None => unit_price,
+        };
+        let amount_due = charged_price
+            .checked_mul(quantity as i128)
+            .ok_or(TicketPaymentError::InvalidQuantity)?;
+        if amount < amount_due {
+            return Err(TicketPaymentError::Underpayment);
+        }
+        if amount > amount_due {
+            return Err(TicketPaymentError::Overpayment);
+        }
+
+        // 2. Calculate platform fee (platform_fee_percent is in bps, 10000 = 100%).
+        // The registry resolves any organizer or event fee override.
+        let platform_fee_percent = platform_fee_percent(&registry_client, &event_id)?;
+        let platform_fee = (amount * platform_fee_percent as i128) / 10000;
         let organizer_amount = amount - platform_fee;
 
         // 3. Transfer tokens to contract (escrow)
@@ -221,35 +527,83 @@ impl TicketPaymentContract {
         }
 
         // 4. Update escrow balances
-        update_event_balance(&env, event_id.clone(), organizer_amount, platform_fee);
+        update_event_balance(
+            &env,
+            event_id.clone(),
+            token_address.clone(),
+            organizer_amount,
+            platform_fee,
+        );
 
-        // 5. Increment inventory after successful payment
-        registry_client.increment_inventory(&event_id, &ticket_tier_id);
+        // 5. Increment inventory for the whole batch after successful payment
+        registry_client.increment_inventory_bulk(
+            &event_id,
+            &ticket_tier_id,
+            &buyer_address,
+            &quantity,
+        );
 
         // 6. Create payment record
         let payment = Payment {
             payment_id: payment_id.clone(),
             event_id: event_id.clone(),
             buyer_address: buyer_address.clone(),
-            ticket_tier_id,
+            token_address: token_address.clone(),
+            ticket_tier_id: ticket_tier_id.clone(),
+            tier_price: unit_price,
+            quantity,
             amount,
             platform_fee,
             organizer_amount,
+            refunded_amount: 0,
             status: PaymentStatus::Pending,
             transaction_hash: String::from_str(&env, ""), // Empty until confirmed
             created_at: env.ledger().timestamp(),
             confirmed_at: None,
+            promo_code_hash,
         };
 
         store_payment(&env, payment);
+        set_buyer_purchases(&env, &event_id, &buyer_address, &purchases);
+
+        // 7. Issue one ticket per unit purchased, owned by the buyer
+        let mut ticket_ids = Vec::new(&env);
+        for _ in 0..quantity {
+            let ticket = Ticket {
+                ticket_id: next_ticket_id(&env),
+                payment_id: payment_id.clone(),
+                event_id: event_id.clone(),
+                ticket_tier_id: ticket_tier_id.clone(),
+                owner: buyer_address.clone(),
+                status: TicketStatus::Active,
+                issued_at: env.ledger().timestamp(),
+                checked_in_at: None,
+            };
+            store_ticket(&env, &ticket);
+            add_owner_ticket(&env, &buyer_address, ticket.ticket_id);
+            ticket_ids.push_back(ticket.ticket_id);
+        }
+        set_payment_tickets(&env, payment_id.clone(), &ticket_ids);
+
+        env.events().publish(
+            (AgoraEvent::TicketsIssued,),
+            TicketsIssuedEvent {
+                payment_id: payment_id.clone(),
+                event_id: event_id.clone(),
+                owner: buyer_address.clone(),
+                ticket_ids,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
-        // 7. Emit payment event
+        // 8. Emit payment event
         env.events().publish(
             (AgoraEvent::PaymentProcessed,),
             PaymentProcessedEvent {
                 payment_id: payment_id.clone(),
                 event_id: event_id.clone(),
                 buyer_address: buyer_address.clone(),
+                quantity,
                 amount,
                 platform_fee,
                 timestamp: env.ledger().timestamp(),
@@ -291,12 +645,18 @@ impl TicketPaymentContract {
         );
     }
 
+    /// Refunds a payment to its buyer. The refundable share is paid back from escrow and
+    /// the tickets are returned to inventory. A `Cancelled` event is refunded in full and
+    /// a `Completed` one is rejected with `EventInactive`. Otherwise the share follows the
+    /// tier's refund schedule at the current ledger timestamp; the rest stays in escrow,
+    /// split between organizer and platform at the original fee ratio. Fails with
+    /// `InsufficientEscrowBalance` if the shares to refund have already been withdrawn.
     pub fn request_guest_refund(env: Env, payment_id: String) -> Result<(), TicketPaymentError> {
         if !is_initialized(&env) {
             panic!("Contract not initialized");
         }
 
-        let mut payment =
+        let payment =
             get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
 
         payment.buyer_address.require_auth();
@@ -305,6 +665,22 @@ impl TicketPaymentContract {
             return Err(TicketPaymentError::InvalidPaymentStatus);
         }
 
+        // Tickets handed on to someone else or already used at the door
+        // can no longer be refunded by the buyer
+        for ticket_id in get_payment_tickets(&env, payment_id.clone()).iter() {
+            if let Some(ticket) = get_ticket(&env, ticket_id) {
+                if ticket.status == TicketStatus::CheckedIn {
+                    return Err(TicketPaymentError::TicketAlreadyCheckedIn);
+                }
+                if get_listing(&env, ticket_id).is_some() {
+                    return Err(TicketPaymentError::TicketListed);
+                }
+                if ticket.owner != payment.buyer_address {
+                    return Err(TicketPaymentError::TicketTransferred);
+                }
+            }
+        }
+
         let event_registry_addr = get_event_registry(&env);
         let registry_client = event_registry::Client::new(&env, &event_registry_addr);
 
@@ -318,28 +694,268 @@ impl TicketPaymentContract {
             .get(payment.ticket_tier_id.clone())
             .ok_or(TicketPaymentError::TierNotFound)?;
 
-        // Check if refundable or if EVENT IS CANCELLED (is_active == false)
-        if !tier.is_refundable && event_info.is_active {
+        // A cancelled event is always refunded in full and a completed one not at all.
+        // Otherwise the tier's refund schedule decides the share returned.
+        let refund_bps = match event_info.status {
+            event_registry::EventStatus::Cancelled => FULL_REFUND_BPS,
+            event_registry::EventStatus::Completed => {
+                return Err(TicketPaymentError::EventInactive)
+            }
+            _ => {
+                if !tier.is_refundable {
+                    return Err(TicketPaymentError::TicketNotRefundable);
+                }
+                refund_bps_at(&tier, env.ledger().timestamp())
+            }
+        };
+        if refund_bps == 0 {
             return Err(TicketPaymentError::TicketNotRefundable);
         }
 
-        // Return ticket to inventory using the authorized contract interface
-        registry_client.decrement_inventory(&payment.event_id, &payment.ticket_tier_id);
+        settle_refund(&env, &registry_client, payment, refund_bps)?;
 
-        let old_status = payment.status.clone();
-        payment.status = PaymentStatus::Refunded;
-        payment.confirmed_at = Some(env.ledger().timestamp());
+        Ok(())
+    }
 
-        store_payment(&env, payment);
+    /// Refunds the payments of an event in `EventStatus::Cancelled` in bounded pages.
+    ///
+    /// Walks the event's payment index from a stored cursor, visiting at most `batch_size`
+    /// entries, and refunds every pending or confirmed payment. Each ticket's share goes to
+    /// its current holder, so transferred and resold tickets are refunded to whoever holds
+    /// them. Payments whose escrow has already been withdrawn are skipped. Call repeatedly
+    /// until `completed` is true.
+    ///
+    /// # Arguments
+    /// * `caller` - The event organizer or the contract administrator.
+    /// * `event_id` - The cancelled event.
+    /// * `batch_size` - Number of payments to visit, up to `MAX_REFUND_BATCH_SIZE`.
+    pub fn refund_cancelled_event(
+        env: Env,
+        caller: Address,
+        event_id: String,
+        batch_size: u32,
+    ) -> Result<RefundBatchResult, TicketPaymentError> {
+        if !is_initialized(&env) {
+            return Err(TicketPaymentError::NotInitialized);
+        }
+        caller.require_auth();
+
+        if batch_size == 0 || batch_size > MAX_REFUND_BATCH_SIZE {
+            return Err(TicketPaymentError::InvalidBatchSize);
+        }
+
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+        let event_info = match registry_client.try_get_event(&event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+
+        let admin = get_admin(&env).ok_or(TicketPaymentError::NotInitialized)?;
+        if caller != event_info.organizer_address && caller != admin {
+            return Err(TicketPaymentError::Unauthorized);
+        }
+
+        if event_info.status != event_registry::EventStatus::Cancelled {
+            return Err(TicketPaymentError::EventNotCancelled);
+        }
+
+        let payment_ids = get_event_payments(&env, event_id.clone());
+        let total_payments = payment_ids.len();
+        let start = get_refund_cursor(&env, event_id.clone()).min(total_payments);
+        let end = start.saturating_add(batch_size).min(total_payments);
+
+        let mut result = RefundBatchResult {
+            processed: end - start,
+            refunded: 0,
+            skipped: 0,
+            amount_refunded: 0,
+            next_cursor: end,
+            total_payments,
+            completed: end == total_payments,
+        };
+
+        for index in start..end {
+            let payment_id = payment_ids.get_unchecked(index);
+            let payment = match get_payment(&env, payment_id) {
+                Some(payment) => payment,
+                None => continue,
+            };
+            if payment.status != PaymentStatus::Pending
+                && payment.status != PaymentStatus::Confirmed
+            {
+                continue;
+            }
+
+            match settle_refund(&env, &registry_client, payment, FULL_REFUND_BPS) {
+                Ok(amount) => {
+                    result.refunded += 1;
+                    result.amount_refunded += amount;
+                }
+                Err(TicketPaymentError::InsufficientEscrowBalance) => result.skipped += 1,
+                Err(e) => return Err(e),
+            }
+        }
+
+        set_refund_cursor(&env, event_id.clone(), end);
 
-        // Emit confirmation event
         env.events().publish(
-            (AgoraEvent::PaymentStatusChanged,),
-            PaymentStatusChangedEvent {
-                payment_id: payment_id.clone(),
-                old_status,
-                new_status: PaymentStatus::Refunded,
-                transaction_hash: String::from_str(&env, "refund"),
+            (AgoraEvent::CancellationRefundProgress,),
+            CancellationRefundProgressEvent {
+                event_id,
+                triggered_by: caller,
+                refunded: result.refunded,
+                skipped: result.skipped,
+                amount_refunded: result.amount_refunded,
+                next_cursor: result.next_cursor,
+                total_payments,
+                completed: result.completed,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(result)
+    }
+
+    /// Returns the index into the event's payment list where the next
+    /// `refund_cancelled_event` batch will resume.
+    pub fn get_refund_cursor(env: Env, event_id: String) -> u32 {
+        get_refund_cursor(&env, event_id)
+    }
+
+    /// Transfers a ticket to a new owner. Only the current owner can transfer, and only
+    /// while the ticket is active.
+    pub fn transfer_ticket(
+        env: Env,
+        ticket_id: u64,
+        to: Address,
+    ) -> Result<(), TicketPaymentError> {
+        let mut ticket = get_ticket(&env, ticket_id).ok_or(TicketPaymentError::TicketNotFound)?;
+        let from = ticket.owner.clone();
+        from.require_auth();
+
+        if ticket.status != TicketStatus::Active {
+            return Err(TicketPaymentError::TicketNotActive);
+        }
+        if get_listing(&env, ticket_id).is_some() {
+            return Err(TicketPaymentError::TicketListed);
+        }
+        if to == from {
+            return Err(TicketPaymentError::InvalidAddress);
+        }
+        validate_address(&env, &to)?;
+
+        remove_owner_ticket(&env, &from, ticket_id);
+        add_owner_ticket(&env, &to, ticket_id);
+        ticket.owner = to.clone();
+        store_ticket(&env, &ticket);
+
+        env.events().publish(
+            (AgoraEvent::TicketTransferred,),
+            TicketTransferredEvent {
+                ticket_id,
+                event_id: ticket.event_id,
+                from,
+                to,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Redeems a ticket at the door. Callable by anyone holding a role on the event
+    /// in the registry (owner, manager or scanner). A ticket can be checked in once,
+    /// and refunded tickets cannot be checked in.
+    pub fn check_in_ticket(
+        env: Env,
+        caller: Address,
+        ticket_id: u64,
+    ) -> Result<(), TicketPaymentError> {
+        caller.require_auth();
+
+        let mut ticket = get_ticket(&env, ticket_id).ok_or(TicketPaymentError::TicketNotFound)?;
+
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+        if event_role(&registry_client, &ticket.event_id, &caller).is_none() {
+            return Err(TicketPaymentError::Unauthorized);
+        }
+
+        match ticket.status {
+            TicketStatus::Active => {}
+            TicketStatus::CheckedIn => return Err(TicketPaymentError::TicketAlreadyCheckedIn),
+            TicketStatus::Refunded => return Err(TicketPaymentError::TicketRefunded),
+        }
+
+        let now = env.ledger().timestamp();
+        ticket.status = TicketStatus::CheckedIn;
+        ticket.checked_in_at = Some(now);
+        store_ticket(&env, &ticket);
+
+        // A used ticket can no longer be sold
+        remove_listing(&env, ticket_id);
+
+        env.events().publish(
+            (AgoraEvent::TicketCheckedIn,),
+            TicketCheckedInEvent {
+                ticket_id,
+                event_id: ticket.event_id,
+                checked_in_by: caller,
+                timestamp: now,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Lists a ticket for resale. Only the owner can list, the tier must allow resale
+    /// and `price` cannot exceed the tier's resale cap.
+    pub fn list_ticket(
+        env: Env,
+        ticket_id: u64,
+        price: i128,
+        token_address: Address,
+    ) -> Result<(), TicketPaymentError> {
+        let ticket = get_ticket(&env, ticket_id).ok_or(TicketPaymentError::TicketNotFound)?;
+        ticket.owner.require_auth();
+
+        if ticket.status != TicketStatus::Active {
+            return Err(TicketPaymentError::TicketNotActive);
+        }
+        if !is_token_whitelisted(&env, &token_address) {
+            return Err(TicketPaymentError::TokenNotWhitelisted);
+        }
+
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+        let event_info = match registry_client.try_get_event(&ticket.event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+        let tier = event_info
+            .tiers
+            .get(ticket.ticket_tier_id.clone())
+            .ok_or(TicketPaymentError::TierNotFound)?;
+        validate_resale_price(&tier, price)?;
+
+        let listing = Listing {
+            ticket_id,
+            seller: ticket.owner.clone(),
+            price,
+            token_address: token_address.clone(),
+            listed_at: env.ledger().timestamp(),
+        };
+        set_listing(&env, &listing);
+
+        env.events().publish(
+            (AgoraEvent::TicketListed,),
+            TicketListedEvent {
+                ticket_id,
+                event_id: ticket.event_id,
+                seller: ticket.owner,
+                price,
+                token_address,
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -347,22 +963,269 @@ impl TicketPaymentContract {
         Ok(())
     }
 
+    /// Withdraws a resale listing. Only the seller can cancel.
+    pub fn cancel_listing(env: Env, ticket_id: u64) -> Result<(), TicketPaymentError> {
+        let listing = get_listing(&env, ticket_id).ok_or(TicketPaymentError::ListingNotFound)?;
+        listing.seller.require_auth();
+
+        remove_listing(&env, ticket_id);
+
+        env.events().publish(
+            (AgoraEvent::ListingCancelled,),
+            ListingCancelledEvent {
+                ticket_id,
+                seller: listing.seller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Buys a listed ticket. The buyer pays the listing price in the listing's token;
+    /// the platform fee goes to the platform wallet, the tier royalty to the event's
+    /// payment address and the rest to the seller, all in one transaction. Ownership
+    /// then moves to the buyer.
+    pub fn buy_listing(
+        env: Env,
+        ticket_id: u64,
+        buyer_address: Address,
+    ) -> Result<(), TicketPaymentError> {
+        ensure_not_paused(&env)?;
+        buyer_address.require_auth();
+
+        let listing = get_listing(&env, ticket_id).ok_or(TicketPaymentError::ListingNotFound)?;
+        let mut ticket = get_ticket(&env, ticket_id).ok_or(TicketPaymentError::TicketNotFound)?;
+
+        if ticket.status != TicketStatus::Active || ticket.owner != listing.seller {
+            return Err(TicketPaymentError::TicketNotActive);
+        }
+        if buyer_address == listing.seller {
+            return Err(TicketPaymentError::InvalidAddress);
+        }
+        if !is_token_whitelisted(&env, &listing.token_address) {
+            return Err(TicketPaymentError::TokenNotWhitelisted);
+        }
+
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+        let event_info = match registry_client.try_get_event(&ticket.event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+        if event_info.status != event_registry::EventStatus::Published {
+            return Err(TicketPaymentError::EventInactive);
+        }
+        let tier = event_info
+            .tiers
+            .get(ticket.ticket_tier_id.clone())
+            .ok_or(TicketPaymentError::TierNotFound)?;
+
+        // The organizer may have tightened the policy since the ticket was listed
+        validate_resale_price(&tier, listing.price)?;
+
+        let platform_fee_percent = platform_fee_percent(&registry_client, &ticket.event_id)?;
+        let platform_fee = (listing.price * platform_fee_percent as i128) / 10000;
+        let royalty = (listing.price * tier.royalty_bps as i128) / 10000;
+        let seller_amount = listing.price - platform_fee - royalty;
+        if seller_amount < 0 {
+            return Err(TicketPaymentError::InvalidResalePrice);
+        }
+
+        let token_client = token::Client::new(&env, &listing.token_address);
+        let contract_address = env.current_contract_address();
+        if token_client.allowance(&buyer_address, &contract_address) < listing.price {
+            return Err(TicketPaymentError::InsufficientAllowance);
+        }
+
+        let payouts = [
+            (get_platform_wallet(&env), platform_fee),
+            (event_info.payment_address, royalty),
+            (listing.seller.clone(), seller_amount),
+        ];
+        for (recipient, amount) in payouts.iter() {
+            if *amount > 0 {
+                token_client.transfer_from(&contract_address, &buyer_address, recipient, amount);
+            }
+        }
+
+        remove_listing(&env, ticket_id);
+        remove_owner_ticket(&env, &listing.seller, ticket_id);
+        add_owner_ticket(&env, &buyer_address, ticket_id);
+        ticket.owner = buyer_address.clone();
+        store_ticket(&env, &ticket);
+
+        env.events().publish(
+            (AgoraEvent::TicketResold,),
+            TicketResoldEvent {
+                ticket_id,
+                event_id: ticket.event_id.clone(),
+                seller: listing.seller.clone(),
+                buyer: buyer_address.clone(),
+                token_address: listing.token_address,
+                price: listing.price,
+                royalty,
+                platform_fee,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        env.events().publish(
+            (AgoraEvent::TicketTransferred,),
+            TicketTransferredEvent {
+                ticket_id,
+                event_id: ticket.event_id,
+                from: listing.seller,
+                to: buyer_address,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns how many tickets of an event a buyer has bought, refunds excluded.
+    pub fn get_buyer_purchases(env: Env, event_id: String, buyer: Address) -> BuyerPurchases {
+        get_buyer_purchases(&env, &event_id, &buyer)
+    }
+
+    /// Returns the resale listing of a ticket, if any.
+    pub fn get_listing(env: Env, ticket_id: u64) -> Option<Listing> {
+        get_listing(&env, ticket_id)
+    }
+
+    /// Returns the current owner of a ticket.
+    pub fn owner_of(env: Env, ticket_id: u64) -> Result<Address, TicketPaymentError> {
+        get_ticket(&env, ticket_id)
+            .map(|ticket| ticket.owner)
+            .ok_or(TicketPaymentError::TicketNotFound)
+    }
+
+    /// Returns the ids of the tickets currently held by an owner.
+    pub fn tickets_of(env: Env, owner: Address) -> Vec<u64> {
+        get_owner_tickets(&env, &owner)
+    }
+
+    /// Returns a ticket by its id.
+    pub fn get_ticket(env: Env, ticket_id: u64) -> Option<Ticket> {
+        get_ticket(&env, ticket_id)
+    }
+
+    /// Returns the ids of the tickets issued for a payment.
+    pub fn get_payment_tickets(env: Env, payment_id: String) -> Vec<u64> {
+        get_payment_tickets(&env, payment_id)
+    }
+
     /// Returns the status and details of a payment.
     pub fn get_payment_status(env: Env, payment_id: String) -> Option<Payment> {
         get_payment(&env, payment_id)
     }
 
-    /// Returns the escrowed balance for an event.
-    pub fn get_event_escrow_balance(env: Env, event_id: String) -> crate::types::EventBalance {
-        get_event_balance(&env, event_id)
+    /// Returns the escrowed balance for an event in a single token.
+    pub fn get_event_escrow_balance(
+        env: Env,
+        event_id: String,
+        token_address: Address,
+    ) -> crate::types::EventBalance {
+        get_event_balance(&env, event_id, token_address)
+    }
+
+    /// Returns the escrowed balances for an event, keyed by token.
+    pub fn get_event_escrow_balances(
+        env: Env,
+        event_id: String,
+    ) -> Map<Address, crate::types::EventBalance> {
+        let mut balances = Map::new(&env);
+        for token in get_event_tokens(&env, event_id.clone()).iter() {
+            let balance = get_event_balance(&env, event_id.clone(), token.clone());
+            balances.set(token, balance);
+        }
+        balances
+    }
+
+    /// Sets the dispute window, in seconds, that organizer funds stay in escrow after
+    /// an event ends. Only callable by the administrator.
+    pub fn set_release_hold_period(env: Env, hold_seconds: u64) -> Result<(), TicketPaymentError> {
+        let admin = get_admin(&env).ok_or(TicketPaymentError::NotInitialized)?;
+        admin.require_auth();
+
+        set_release_hold_period(&env, hold_seconds);
+
+        env.events().publish(
+            (AgoraEvent::HoldPeriodUpdated,),
+            HoldPeriodUpdatedEvent {
+                hold_seconds,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the dispute window applied after an event ends, in seconds.
+    pub fn get_release_hold_period(env: Env) -> u64 {
+        get_release_hold_period(&env)
+    }
+
+    /// Marks an organizer as trusted, letting them withdraw before the event ends.
+    /// Only callable by the administrator.
+    pub fn set_trusted_organizer(
+        env: Env,
+        organizer: Address,
+        trusted: bool,
+    ) -> Result<(), TicketPaymentError> {
+        let admin = get_admin(&env).ok_or(TicketPaymentError::NotInitialized)?;
+        admin.require_auth();
+
+        set_trusted_organizer(&env, &organizer, trusted);
+
+        env.events().publish(
+            (AgoraEvent::OrganizerTrustUpdated,),
+            OrganizerTrustUpdatedEvent {
+                organizer,
+                trusted,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns whether an organizer may withdraw before the escrow unlock time.
+    pub fn is_trusted_organizer(env: Env, organizer: Address) -> bool {
+        is_trusted_organizer(&env, &organizer)
     }
 
-    /// Withdraw organizer funds from escrow.
+    /// Returns the timestamp from which the organizer can withdraw an event's funds:
+    /// the event end time plus the hold period, or 0 for trusted organizers.
+    pub fn get_funds_unlock_time(env: Env, event_id: String) -> Result<u64, TicketPaymentError> {
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+        let event_info = match registry_client.try_get_event(&event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+
+        if is_trusted_organizer(&env, &event_info.organizer_address) {
+            return Ok(0);
+        }
+        Ok(funds_unlock_time(&env, &event_info))
+    }
+
+    /// Withdraw organizer funds from escrow to the event's payment address. Callable by
+    /// the event's owner or managers. Only the amount collected in `token_address` is
+    /// released, and only once the event has ended and the hold period has passed,
+    /// unless the organizer is trusted. Nothing is released for a cancelled event, whose
+    /// escrow is owed back to its ticket holders.
     pub fn withdraw_organizer_funds(
         env: Env,
+        caller: Address,
         event_id: String,
         token_address: Address,
     ) -> Result<i128, TicketPaymentError> {
+        ensure_not_paused(&env)?;
+        caller.require_auth();
+
         let event_registry_addr = get_event_registry(&env);
         let registry_client = event_registry::Client::new(&env, &event_registry_addr);
         let event_info = registry_client
@@ -372,22 +1235,29 @@ impl TicketPaymentContract {
             .flatten()
             .ok_or(TicketPaymentError::EventNotFound)?;
 
-        event_info.organizer_address.require_auth();
+        match event_role(&registry_client, &event_id, &caller) {
+            Some(event_registry::EventRole::Owner) | Some(event_registry::EventRole::Manager) => {}
+            _ => return Err(TicketPaymentError::Unauthorized),
+        }
+
+        ensure_funds_releasable(&env, &event_info)?;
 
-        let balance = get_event_balance(&env, event_id.clone());
+        let balance = get_event_balance(&env, event_id.clone(), token_address.clone());
         if balance.organizer_amount == 0 {
             return Ok(0);
         }
 
+        // Paid to the event's current payment address, which follows ownership transfers
         token::Client::new(&env, &token_address).transfer(
             &env.current_contract_address(),
-            &event_info.organizer_address,
+            &event_info.payment_address,
             &balance.organizer_amount,
         );
 
         crate::storage::set_event_balance(
             &env,
             event_id,
+            token_address,
             crate::types::EventBalance {
                 organizer_amount: 0,
                 platform_fee: balance.platform_fee,
@@ -397,16 +1267,29 @@ impl TicketPaymentContract {
         Ok(balance.organizer_amount)
     }
 
-    /// Withdraw platform fees from escrow.
+    /// Withdraw platform fees from escrow. Only the fees collected in `token_address`
+    /// are released, under the same unlock time and cancellation rules as organizer
+    /// funds, so the platform share stays available for refunds until then.
     pub fn withdraw_platform_fees(
         env: Env,
         event_id: String,
         token_address: Address,
     ) -> Result<i128, TicketPaymentError> {
+        ensure_not_paused(&env)?;
         let admin = get_admin(&env).ok_or(TicketPaymentError::NotInitialized)?;
         admin.require_auth();
 
-        let balance = get_event_balance(&env, event_id.clone());
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+        let event_info = registry_client
+            .try_get_event(&event_id)
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten()
+            .ok_or(TicketPaymentError::EventNotFound)?;
+        ensure_funds_releasable(&env, &event_info)?;
+
+        let balance = get_event_balance(&env, event_id.clone(), token_address.clone());
         if balance.platform_fee == 0 {
             return Ok(0);
         }
@@ -421,6 +1304,7 @@ impl TicketPaymentContract {
         crate::storage::set_event_balance(
             &env,
             event_id,
+            token_address,
             crate::types::EventBalance {
                 organizer_amount: balance.organizer_amount,
                 platform_fee: 0,
@@ -431,6 +1315,368 @@ impl TicketPaymentContract {
     }
 }
 
+#[allow(deprecated)]
+fn set_pause_state(env: &Env, paused: bool, reason: u32) -> Result<(), TicketPaymentError> {
+    let admin = get_admin(env).ok_or(TicketPaymentError::NotInitialized)?;
+    admin.require_auth();
+
+    set_paused(env, paused);
+
+    let topic = if paused {
+        AgoraEvent::ContractPaused
+    } else {
+        AgoraEvent::ContractUnpaused
+    };
+    env.events().publish(
+        (topic,),
+        PauseStatusEvent {
+            reason,
+            updated_by: admin,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+fn ensure_not_paused(env: &Env) -> Result<(), TicketPaymentError> {
+    if is_paused(env) {
+        return Err(TicketPaymentError::ContractPaused);
+    }
+    Ok(())
+}
+
+/// Looks up the role `account` holds on an event in the registry.
+fn event_role(
+    registry_client: &event_registry::Client,
+    event_id: &String,
+    account: &Address,
+) -> Option<event_registry::EventRole> {
+    registry_client
+        .try_get_event_role(event_id, account)
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+}
+
+/// Fetches the platform fee that currently applies to an event, including overrides.
+fn platform_fee_percent(
+    registry_client: &event_registry::Client,
+    event_id: &String,
+) -> Result<u32, TicketPaymentError> {
+    match registry_client.try_get_event_payment_info(event_id) {
+        Ok(Ok(payment_info)) => Ok(payment_info.platform_fee_percent),
+        _ => Err(TicketPaymentError::EventNotFound),
+    }
+}
+
+/// Rejects purchases outside the tier's sales window. A tier without its own end
+/// closes when doors open, and no sales run past the event's end; an end of 0
+/// means no bound.
+fn check_sales_window(
+    env: &Env,
+    event_info: &event_registry::EventInfo,
+    tier: &event_registry::TicketTier,
+) -> Result<(), TicketPaymentError> {
+    let now = env.ledger().timestamp();
+    if now < tier.sale_start {
+        return Err(TicketPaymentError::SalesNotStarted);
+    }
+    let sale_end = if tier.sale_end != 0 {
+        tier.sale_end
+    } else {
+        event_info.start_time
+    };
+    let ended = |end: u64| end != 0 && now >= end;
+    if ended(sale_end) || ended(event_info.end_time) {
+        return Err(TicketPaymentError::SalesEnded);
+    }
+    Ok(())
+}
+
+/// Rejects batches that would cross a sold-count price step of the tier; the whole
+/// batch is charged the unit price of its first ticket.
+fn check_price_steps(
+    tier: &event_registry::TicketTier,
+    quantity: u32,
+) -> Result<(), TicketPaymentError> {
+    let new_sold = tier.current_sold.saturating_add(quantity as i128);
+    for step in tier.price_schedule.iter() {
+        if let event_registry::PriceTrigger::Sold(count) = step.trigger {
+            if tier.current_sold < count && count < new_sold {
+                return Err(TicketPaymentError::PriceStepCrossed);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Adds `quantity` tickets to a buyer's counts, enforcing the event and tier
+/// `max_per_buyer` limits (0 = no limit).
+fn add_purchases(
+    event_info: &event_registry::EventInfo,
+    tier: &event_registry::TicketTier,
+    tier_id: &String,
+    purchases: &mut BuyerPurchases,
+    quantity: u32,
+) -> Result<(), TicketPaymentError> {
+    let total = purchases
+        .total
+        .checked_add(quantity)
+        .ok_or(TicketPaymentError::BuyerLimitExceeded)?;
+    let tier_count = purchases
+        .tiers
+        .get(tier_id.clone())
+        .unwrap_or(0)
+        .checked_add(quantity)
+        .ok_or(TicketPaymentError::BuyerLimitExceeded)?;
+
+    let exceeds = |limit: u32, count: u32| limit > 0 && count > limit;
+    if exceeds(event_info.max_per_buyer, total) || exceeds(tier.max_per_buyer, tier_count) {
+        return Err(TicketPaymentError::BuyerLimitExceeded);
+    }
+
+    purchases.total = total;
+    purchases.tiers.set(tier_id.clone(), tier_count);
+    Ok(())
+}
+
+/// Enforces a tier's access rule for a buyer.
+fn check_tier_access(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    event_id: &String,
+    tier_id: &String,
+    tier: &event_registry::TicketTier,
+    buyer: &Address,
+    access_proof: Option<Vec<BytesN<32>>>,
+) -> Result<(), TicketPaymentError> {
+    let allowed = match &tier.access_rule {
+        event_registry::AccessRule::Open => true,
+        event_registry::AccessRule::Allowlist => registry_client
+            .try_is_allowlisted(event_id, tier_id, buyer)
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(false),
+        event_registry::AccessRule::MerkleRoot(root) => match access_proof {
+            Some(proof) => verify_merkle_proof(env, root, buyer, &proof),
+            None => false,
+        },
+        event_registry::AccessRule::TokenHolder(token, min_balance) => {
+            token::Client::new(env, token).balance(buyer) >= *min_balance
+        }
+    };
+    if !allowed {
+        return Err(TicketPaymentError::TierAccessDenied);
+    }
+    Ok(())
+}
+
+/// Checks that `buyer` is a leaf of the Merkle tree with `root`. Leaves are the
+/// SHA-256 of the XDR-encoded address and each pair is hashed in sorted order.
+fn verify_merkle_proof(
+    env: &Env,
+    root: &BytesN<32>,
+    buyer: &Address,
+    proof: &Vec<BytesN<32>>,
+) -> bool {
+    let mut computed: BytesN<32> = env.crypto().sha256(&buyer.clone().to_xdr(env)).into();
+    for sibling in proof.iter() {
+        let mut pair = Bytes::new(env);
+        if computed <= sibling {
+            pair.append(&computed.clone().into());
+            pair.append(&sibling.into());
+        } else {
+            pair.append(&sibling.into());
+            pair.append(&computed.clone().into());
+        }
+        computed = env.crypto().sha256(&pair).into();
+    }
+    computed == *root
+}
+
+/// Applies a promo code discount to a ticket price, never going below zero.
+fn discounted_price(unit_price: i128, discount: &event_registry::Discount) -> i128 {
+    let discount = match discount {
+        event_registry::Discount::Percentage(bps) => (unit_price * *bps as i128) / 10000,
+        event_registry::Discount::Fixed(amount) => *amount,
+    };
+    (unit_price - discount).max(0)
+}
+
+/// Checks a resale price against the tier's resale cap.
+fn validate_resale_price(
+    tier: &event_registry::TicketTier,
+    price: i128,
+) -> Result<(), TicketPaymentError> {
+    if tier.max_resale_bps == 0 {
+        return Err(TicketPaymentError::ResaleNotAllowed);
+    }
+    let max_price = (tier.price * tier.max_resale_bps as i128) / 10000;
+    if price <= 0 || price > max_price {
+        return Err(TicketPaymentError::InvalidResalePrice);
+    }
+    Ok(())
+}
+
+/// Returns the timestamp at which an event's organizer funds leave the dispute window.
+fn funds_unlock_time(env: &Env, event_info: &event_registry::EventInfo) -> u64 {
+    event_info
+        .end_time
+        .saturating_add(get_release_hold_period(env))
+}
+
+/// Ensures an event's escrow can be paid out: never for a cancelled event, whose
+/// escrow is owed to its ticket holders, and otherwise only once the funds unlock,
+/// unless the organizer is trusted.
+fn ensure_funds_releasable(
+    env: &Env,
+    event_info: &event_registry::EventInfo,
+) -> Result<(), TicketPaymentError> {
+    if event_info.status == event_registry::EventStatus::Cancelled {
+        return Err(TicketPaymentError::EventCancelled);
+    }
+    if !is_trusted_organizer(env, &event_info.organizer_address)
+        && env.ledger().timestamp() < funds_unlock_time(env, event_info)
+    {
+        return Err(TicketPaymentError::FundsLocked);
+    }
+    Ok(())
+}
+
+/// Returns the refund share in basis points that a tier's schedule grants at `now`.
+/// Tiers without a schedule are refunded in full.
+fn refund_bps_at(tier: &event_registry::TicketTier, now: u64) -> u32 {
+    if tier.refund_schedule.is_empty() {
+        return FULL_REFUND_BPS;
+    }
+    for window in tier.refund_schedule.iter() {
+        if now <= window.deadline {
+            return window.refund_bps;
+        }
+    }
+    0
+}
+
+/// Pays `refund_bps` of a payment back to the buyer from escrow, returns the tickets to
+/// inventory and marks the payment as refunded. Each escrow share is reduced in
+/// proportion, so the kept amount stays split at the original fee ratio.
+/// Returns the amount refunded.
+#[allow(deprecated)]
+fn settle_refund(
+    env: &Env,
+    registry_client: &event_registry::Client,
+    mut payment: Payment,
+    refund_bps: u32,
+) -> Result<i128, TicketPaymentError> {
+    let organizer_refund = (payment.organizer_amount * refund_bps as i128) / 10000;
+    let platform_refund = (payment.platform_fee * refund_bps as i128) / 10000;
+    let refund_amount = organizer_refund + platform_refund;
+
+    // Both refunded shares must still be held in escrow
+    let balance = get_event_balance(env, payment.event_id.clone(), payment.token_address.clone());
+    if balance.organizer_amount < organizer_refund || balance.platform_fee < platform_refund {
+        return Err(TicketPaymentError::InsufficientEscrowBalance);
+    }
+
+    crate::storage::set_event_balance(
+        env,
+        payment.event_id.clone(),
+        payment.token_address.clone(),
+        crate::types::EventBalance {
+            organizer_amount: balance.organizer_amount - organizer_refund,
+            platform_fee: balance.platform_fee - platform_refund,
+        },
+    );
+
+    // Return every ticket in the payment to inventory using the authorized contract interface
+    registry_client.decrement_inventory_bulk(
+        &payment.event_id,
+        &payment.ticket_tier_id,
+        &payment.quantity,
+    );
+
+    // Refunded tickets no longer count towards the buyer's limits
+    let mut purchases = get_buyer_purchases(env, &payment.event_id, &payment.buyer_address);
+    purchases.total = purchases.total.saturating_sub(payment.quantity);
+    let tier_count = purchases
+        .tiers
+        .get(payment.ticket_tier_id.clone())
+        .unwrap_or(0);
+    purchases.tiers.set(
+        payment.ticket_tier_id.clone(),
+        tier_count.saturating_sub(payment.quantity),
+    );
+    set_buyer_purchases(env, &payment.event_id, &payment.buyer_address, &purchases);
+
+    // ...and no longer use up the promo code they were bought with
+    if let Some(code_hash) = &payment.promo_code_hash {
+        registry_client.credit_promo_code(&payment.event_id, code_hash, &payment.quantity);
+    }
+
+    // Each ticket's current holder gets its share of the refund, so tickets given away
+    // or resold are refunded to whoever holds them now. The payment's tickets are void
+    // and leave their holders' lists.
+    let token_client = token::Client::new(env, &payment.token_address);
+    let share = refund_amount / payment.quantity.max(1) as i128;
+    let mut remaining = refund_amount;
+    for ticket_id in get_payment_tickets(env, payment.payment_id.clone()).iter() {
+        if let Some(mut ticket) = get_ticket(env, ticket_id) {
+            if share > 0 {
+                token_client.transfer(&env.current_contract_address(), &ticket.owner, &share);
+                remaining -= share;
+            }
+            remove_owner_ticket(env, &ticket.owner, ticket_id);
+            remove_listing(env, ticket_id);
+            ticket.status = TicketStatus::Refunded;
+            store_ticket(env, &ticket);
+        }
+    }
+    // Rounding dust, and payments recorded before tickets were issued, go to the buyer
+    if remaining > 0 {
+        token_client.transfer(
+            &env.current_contract_address(),
+            &payment.buyer_address,
+            &remaining,
+        );
+    }
+
+    let old_status = payment.status.clone();
+    payment.status = PaymentStatus::Refunded;
+    payment.refunded_amount = refund_amount;
+    payment.confirmed_at = Some(env.ledger().timestamp());
+
+    let payment_id = payment.payment_id.clone();
+    let event_id = payment.event_id.clone();
+    let buyer_address = payment.buyer_address.clone();
+    store_payment(env, payment);
+
+    env.events().publish(
+        (AgoraEvent::PaymentStatusChanged,),
+        PaymentStatusChangedEvent {
+            payment_id: payment_id.clone(),
+            old_status,
+            new_status: PaymentStatus::Refunded,
+            transaction_hash: String::from_str(env, "refund"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    env.events().publish(
+        (AgoraEvent::RefundProcessed,),
+        RefundProcessedEvent {
+            payment_id,
+            event_id,
+            buyer_address,
+            amount: refund_amount,
+            refund_bps,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(refund_amount)
+}
+
 fn validate_address(env: &Env, address: &Address) -> Result<(), TicketPaymentError> {
     if address == &env.current_contract_address() {
         return Err(TicketPaymentError::InvalidAddress);
@@ -17,6 +17,34 @@ pub enum TicketPaymentError {
     TierNotFound = 11,
     InsufficientAllowance = 12,
     TransferVerificationFailed = 13,
+    Underpayment = 14,
+    Overpayment = 15,
+    InvalidQuantity = 16,
+    InsufficientEscrowBalance = 17,
+    Unauthorized = 18,
+    EventNotCancelled = 19,
+    InvalidBatchSize = 20,
+    FundsLocked = 21,
+    PaymentAlreadyExists = 22,
+    TicketNotFound = 23,
+    TicketNotActive = 24,
+    TicketTransferred = 25,
+    TicketAlreadyCheckedIn = 26,
+    TicketRefunded = 27,
+    ResaleNotAllowed = 28,
+    InvalidResalePrice = 29,
+    ListingNotFound = 30,
+    TicketListed = 31,
+    SalesNotStarted = 32,
+    SalesEnded = 33,
+    TierRetired = 34,
+    NoPendingAdmin = 35,
+    ContractPaused = 36,
+    InvalidPromoCode = 37,
+    BuyerLimitExceeded = 38,
+    TierAccessDenied = 39,
+    EventCancelled = 40,
+    PriceStepCrossed = 41,
 }
 
 impl core::fmt::Display for TicketPaymentError {
@@ -43,6 +71,65 @@ impl core::fmt::Display for TicketPaymentError {
             TicketPaymentError::TransferVerificationFailed => {
                 write!(f, "Transfer verification failed")
             }
+            TicketPaymentError::Underpayment => {
+                write!(f, "Payment amount is below the amount due for the tier")
+            }
+            TicketPaymentError::Overpayment => {
+                write!(f, "Payment amount exceeds the amount due for the tier")
+            }
+            TicketPaymentError::InvalidQuantity => {
+                write!(f, "Ticket quantity must be greater than zero")
+            }
+            TicketPaymentError::InsufficientEscrowBalance => {
+                write!(f, "Escrow balance has already been withdrawn")
+            }
+            TicketPaymentError::Unauthorized => write!(f, "Caller not authorized for action"),
+            TicketPaymentError::EventNotCancelled => {
+                write!(f, "Event must be cancelled before bulk refunds")
+            }
+            TicketPaymentError::InvalidBatchSize => {
+                write!(f, "Batch size must be between 1 and the maximum batch size")
+            }
+            TicketPaymentError::FundsLocked => {
+                write!(
+                    f,
+                    "Funds are locked until the event ends and the hold period passes"
+                )
+            }
+            TicketPaymentError::PaymentAlreadyExists => write!(f, "Payment already exists"),
+            TicketPaymentError::TicketNotFound => write!(f, "Ticket not found"),
+            TicketPaymentError::TicketNotActive => write!(f, "Ticket is not active"),
+            TicketPaymentError::TicketTransferred => {
+                write!(f, "Ticket has been transferred away from the buyer")
+            }
+            TicketPaymentError::TicketAlreadyCheckedIn => {
+                write!(f, "Ticket has already been checked in")
+            }
+            TicketPaymentError::TicketRefunded => write!(f, "Ticket has been refunded"),
+            TicketPaymentError::ResaleNotAllowed => {
+                write!(f, "Resale is not allowed for this tier")
+            }
+            TicketPaymentError::InvalidResalePrice => {
+                write!(f, "Resale price is outside the allowed range")
+            }
+            TicketPaymentError::ListingNotFound => write!(f, "Listing not found"),
+            TicketPaymentError::TicketListed => write!(f, "Ticket is listed for resale"),
+            TicketPaymentError::SalesNotStarted => write!(f, "Ticket sales have not started"),
+            TicketPaymentError::SalesEnded => write!(f, "Ticket sales have ended"),
+            TicketPaymentError::TierRetired => write!(f, "Ticket tier has been retired"),
+            TicketPaymentError::NoPendingAdmin => write!(f, "No pending admin"),
+            TicketPaymentError::ContractPaused => write!(f, "Contract is paused"),
+            TicketPaymentError::InvalidPromoCode => {
+                write!(f, "Promo code is invalid, expired or exhausted")
+            }
+            TicketPaymentError::BuyerLimitExceeded => {
+                write!(f, "Purchase would exceed the per-buyer limit")
+            }
+            TicketPaymentError::TierAccessDenied => {
+                write!(f, "Buyer does not meet the tier's access rule")
+            }
+            TicketPaymentError::EventCancelled => write!(f, "Event has been cancelled"),
+            TicketPaymentError::PriceStepCrossed => write!(f, "Purchase would cross a price step"),
         }
     }
 }
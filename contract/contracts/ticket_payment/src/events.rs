@@ -1,5 +1,5 @@
 use crate::types::PaymentStatus;
-use soroban_sdk::{contracttype, Address, BytesN, String};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -8,6 +8,21 @@ pub enum AgoraEvent {
     PaymentStatusChanged,
     ContractInitialized,
     ContractUpgraded,
+    RefundProcessed,
+    CancellationRefundProgress,
+    HoldPeriodUpdated,
+    OrganizerTrustUpdated,
+    TicketsIssued,
+    TicketTransferred,
+    TicketCheckedIn,
+    TicketListed,
+    ListingCancelled,
+    TicketResold,
+    AdminProposed,
+    AdminChanged,
+    PlatformWalletUpdated,
+    ContractPaused,
+    ContractUnpaused,
 }
 
 #[contracttype]
@@ -16,6 +31,7 @@ pub struct PaymentProcessedEvent {
     pub payment_id: String,
     pub event_id: String,
     pub buyer_address: Address,
+    pub quantity: u32,
     pub amount: i128,
     pub platform_fee: i128,
     pub timestamp: u64,
@@ -31,6 +47,108 @@ pub struct PaymentStatusChangedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundProcessedEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub buyer_address: Address,
+    pub amount: i128,
+    pub refund_bps: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancellationRefundProgressEvent {
+    pub event_id: String,
+    pub triggered_by: Address,
+    pub refunded: u32,
+    pub skipped: u32,
+    pub amount_refunded: i128,
+    pub next_cursor: u32,
+    pub total_payments: u32,
+    pub completed: bool,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HoldPeriodUpdatedEvent {
+    pub hold_seconds: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrganizerTrustUpdatedEvent {
+    pub organizer: Address,
+    pub trusted: bool,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketsIssuedEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub owner: Address,
+    pub ticket_ids: Vec<u64>,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketTransferredEvent {
+    pub ticket_id: u64,
+    pub event_id: String,
+    pub from: Address,
+    pub to: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketCheckedInEvent {
+    pub ticket_id: u64,
+    pub event_id: String,
+    pub checked_in_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketListedEvent {
+    pub ticket_id: u64,
+    pub event_id: String,
+    pub seller: Address,
+    pub price: i128,
+    pub token_address: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListingCancelledEvent {
+    pub ticket_id: u64,
+    pub seller: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketResoldEvent {
+    pub ticket_id: u64,
+    pub event_id: String,
+    pub seller: Address,
+    pub buyer: Address,
+    pub token_address: Address,
+    pub price: i128,
+    pub royalty: i128,
+    pub platform_fee: i128,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InitializationEvent {
@@ -45,3 +163,35 @@ pub struct ContractUpgraded {
     pub old_wasm_hash: BytesN<32>,
     pub new_wasm_hash: BytesN<32>,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposedEvent {
+    pub current_admin: Address,
+    pub proposed_admin: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminChangedEvent {
+    pub previous_admin: Address,
+    pub new_admin: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformWalletUpdatedEvent {
+    pub old_wallet: Address,
+    pub new_wallet: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseStatusEvent {
+    pub reason: u32,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
@@ -1,5 +1,7 @@
-use crate::types::{DataKey, EventBalance, Payment, PaymentStatus};
-use soroban_sdk::{vec, Address, Env, String, Vec};
+use crate::types::{
+    BuyerPurchases, DataKey, EventBalance, Listing, Payment, PaymentStatus, Ticket,
+};
+use soroban_sdk::{vec, Address, Env, Map, String, Vec};
 
 pub fn set_admin(env: &Env, admin: &Address) {
     env.storage().persistent().set(&DataKey::Admin, admin);
@@ -9,10 +11,41 @@ pub fn get_admin(env: &Env) -> Option<Address> {
     env.storage().persistent().get(&DataKey::Admin)
 }
 
+pub fn set_pending_admin(env: &Env, admin: &Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingAdmin, admin);
+}
+
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::PendingAdmin)
+}
+
+pub fn remove_pending_admin(env: &Env) {
+    env.storage().persistent().remove(&DataKey::PendingAdmin);
+}
+
+pub fn set_paused(env: &Env, paused: bool) {
+    env.storage().persistent().set(&DataKey::Paused, &paused);
+}
+
+pub fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Paused)
+        .unwrap_or(false)
+}
+
 pub fn store_payment(env: &Env, payment: Payment) {
     let key = DataKey::Payment(payment.payment_id.clone());
+    let is_new = !env.storage().persistent().has(&key);
     env.storage().persistent().set(&key, &payment);
 
+    // Updates to an existing payment must not duplicate its index entries
+    if !is_new {
+        return;
+    }
+
     // Index by event
     let event_key = DataKey::EventPayments(payment.event_id.clone());
     let mut event_payments: Vec<String> = env
@@ -58,6 +91,19 @@ pub fn get_event_payments(env: &Env, event_id: String) -> Vec<String> {
     env.storage().persistent().get(&key).unwrap_or(vec![env])
 }
 
+pub fn get_refund_cursor(env: &Env, event_id: String) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RefundCursor(event_id))
+        .unwrap_or(0)
+}
+
+pub fn set_refund_cursor(env: &Env, event_id: String, cursor: u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::RefundCursor(event_id), &cursor);
+}
+
 pub fn get_buyer_payments(env: &Env, buyer_address: Address) -> Vec<String> {
     let key = DataKey::BuyerPayments(buyer_address);
     env.storage().persistent().get(&key).unwrap_or(vec![env])
@@ -135,10 +181,39 @@ pub fn is_token_whitelisted(env: &Env, token: &Address) -> bool {
         .unwrap_or(false)
 }
 
-pub fn get_event_balance(env: &Env, event_id: String) -> EventBalance {
+pub fn set_release_hold_period(env: &Env, hold_seconds: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ReleaseHoldPeriod, &hold_seconds);
+}
+
+pub fn get_release_hold_period(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ReleaseHoldPeriod)
+        .unwrap_or(0)
+}
+
+pub fn set_trusted_organizer(env: &Env, organizer: &Address, trusted: bool) {
+    let key = DataKey::TrustedOrganizer(organizer.clone());
+    if trusted {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn is_trusted_organizer(env: &Env, organizer: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TrustedOrganizer(organizer.clone()))
+        .unwrap_or(false)
+}
+
+pub fn get_event_balance(env: &Env, event_id: String, token: Address) -> EventBalance {
     env.storage()
         .persistent()
-        .get(&DataKey::Balances(event_id))
+        .get(&DataKey::Balances(event_id, token))
         .unwrap_or(EventBalance {
             organizer_amount: 0,
             platform_fee: 0,
@@ -148,19 +223,134 @@ pub fn get_event_balance(env: &Env, event_id: String) -> EventBalance {
 pub fn update_event_balance(
     env: &Env,
     event_id: String,
+    token: Address,
     organizer_amount: i128,
     platform_fee: i128,
 ) {
-    let mut balance = get_event_balance(env, event_id.clone());
+    let mut balance = get_event_balance(env, event_id.clone(), token.clone());
     balance.organizer_amount += organizer_amount;
     balance.platform_fee += platform_fee;
+    set_event_balance(env, event_id, token, balance);
+}
+
+pub fn set_event_balance(env: &Env, event_id: String, token: Address, balance: EventBalance) {
+    // Track every token an event has collected so balances can be listed
+    let mut tokens = get_event_tokens(env, event_id.clone());
+    if !tokens.contains(&token) {
+        tokens.push_back(token.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::EventTokens(event_id.clone()), &tokens);
+    }
+
     env.storage()
         .persistent()
-        .set(&DataKey::Balances(event_id), &balance);
+        .set(&DataKey::Balances(event_id, token), &balance);
 }
 
-pub fn set_event_balance(env: &Env, event_id: String, balance: EventBalance) {
+pub fn get_event_tokens(env: &Env, event_id: String) -> Vec<Address> {
     env.storage()
         .persistent()
-        .set(&DataKey::Balances(event_id), &balance);
+        .get(&DataKey::EventTokens(event_id))
+        .unwrap_or(vec![env])
+}
+
+/// Reserves and returns the next ticket id. Ids start at 1.
+pub fn next_ticket_id(env: &Env) -> u64 {
+    let next: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::TicketCounter)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .persistent()
+        .set(&DataKey::TicketCounter, &next);
+    next
+}
+
+pub fn store_ticket(env: &Env, ticket: &Ticket) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Ticket(ticket.ticket_id), ticket);
+}
+
+pub fn get_ticket(env: &Env, ticket_id: u64) -> Option<Ticket> {
+    env.storage().persistent().get(&DataKey::Ticket(ticket_id))
+}
+
+pub fn get_owner_tickets(env: &Env, owner: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OwnerTickets(owner.clone()))
+        .unwrap_or(vec![env])
+}
+
+pub fn add_owner_ticket(env: &Env, owner: &Address, ticket_id: u64) {
+    let mut tickets = get_owner_tickets(env, owner);
+    tickets.push_back(ticket_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::OwnerTickets(owner.clone()), &tickets);
+}
+
+pub fn remove_owner_ticket(env: &Env, owner: &Address, ticket_id: u64) {
+    let mut tickets = get_owner_tickets(env, owner);
+    if let Some(index) = tickets.first_index_of(ticket_id) {
+        tickets.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::OwnerTickets(owner.clone()), &tickets);
+    }
+}
+
+pub fn set_payment_tickets(env: &Env, payment_id: String, ticket_ids: &Vec<u64>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PaymentTickets(payment_id), ticket_ids);
+}
+
+pub fn get_payment_tickets(env: &Env, payment_id: String) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PaymentTickets(payment_id))
+        .unwrap_or(vec![env])
+}
+
+pub fn set_listing(env: &Env, listing: &Listing) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Listing(listing.ticket_id), listing);
+}
+
+pub fn get_listing(env: &Env, ticket_id: u64) -> Option<Listing> {
+    env.storage().persistent().get(&DataKey::Listing(ticket_id))
+}
+
+pub fn remove_listing(env: &Env, ticket_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Listing(ticket_id));
+}
+
+pub fn set_buyer_purchases(
+    env: &Env,
+    event_id: &String,
+    buyer: &Address,
+    purchases: &BuyerPurchases,
+) {
+    env.storage().persistent().set(
+        &DataKey::BuyerPurchases(event_id.clone(), buyer.clone()),
+        purchases,
+    );
+}
+
+pub fn get_buyer_purchases(env: &Env, event_id: &String, buyer: &Address) -> BuyerPurchases {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BuyerPurchases(event_id.clone(), buyer.clone()))
+        .unwrap_or(BuyerPurchases {
+            total: 0,
+            tiers: Map::new(env),
+        })
 }
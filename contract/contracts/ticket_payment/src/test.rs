@@ -1,22 +1,217 @@
 use super::contract::{event_registry, TicketPaymentContract, TicketPaymentContractClient};
 use super::storage::*;
-use super::types::{Payment, PaymentStatus};
+use super::types::{Payment, PaymentStatus, TicketStatus};
 use crate::error::TicketPaymentError;
 use soroban_sdk::{
-    testutils::{Address as _, Events},
+    testutils::{Address as _, Events, Ledger},
     token, Address, Env, IntoVal, String, Symbol, TryIntoVal,
 };
 
+// Tiers shared by the mock registries. Prices match the amounts used in the tests.
+fn mock_tiers(env: &Env) -> soroban_sdk::Map<String, event_registry::TicketTier> {
+    let mut tiers = soroban_sdk::Map::new(env);
+    tiers.set(
+        String::from_str(env, "tier_1"),
+        event_registry::TicketTier {
+            name: String::from_str(env, "General"),
+            price: 1000_0000000,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule: soroban_sdk::Vec::new(env),
+            max_resale_bps: 12000, // resale up to 120% of face value
+            royalty_bps: 1000,     // 10% to the organizer
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: soroban_sdk::Vec::new(env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: event_registry::AccessRule::Open,
+        },
+    );
+    tiers.set(
+        String::from_str(env, "tier_2"),
+        event_registry::TicketTier {
+            name: String::from_str(env, "Balcony"),
+            price: 500_0000000,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule: soroban_sdk::Vec::new(env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: soroban_sdk::Vec::new(env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: event_registry::AccessRule::Open,
+        },
+    );
+    tiers.set(
+        String::from_str(env, "t1"),
+        event_registry::TicketTier {
+            name: String::from_str(env, "Basic"),
+            price: 10000,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule: soroban_sdk::Vec::new(env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: soroban_sdk::Vec::new(env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: event_registry::AccessRule::Open,
+        },
+    );
+    let mut refund_schedule = soroban_sdk::Vec::new(env);
+    refund_schedule.push_back(event_registry::RefundWindow {
+        deadline: 1000,
+        refund_bps: 10000,
+    });
+    refund_schedule.push_back(event_registry::RefundWindow {
+        deadline: 2000,
+        refund_bps: 5000,
+    });
+    let mut price_schedule = soroban_sdk::Vec::new(env);
+    price_schedule.push_back(event_registry::PriceStep {
+        trigger: event_registry::PriceTrigger::Time(1000),
+        price: 15000,
+    });
+    price_schedule.push_back(event_registry::PriceStep {
+        trigger: event_registry::PriceTrigger::Sold(5),
+        price: 20000,
+    });
+    tiers.set(
+        String::from_str(env, "early_bird"),
+        event_registry::TicketTier {
+            name: String::from_str(env, "Early Bird"),
+            price: 10000,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule: soroban_sdk::Vec::new(env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule,
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: event_registry::AccessRule::Open,
+        },
+    );
+    tiers.set(
+        String::from_str(env, "retired"),
+        event_registry::TicketTier {
+            name: String::from_str(env, "Retired"),
+            price: 10000,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule: soroban_sdk::Vec::new(env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: soroban_sdk::Vec::new(env),
+            is_retired: true,
+            max_per_buyer: 0,
+            access_rule: event_registry::AccessRule::Open,
+        },
+    );
+    tiers.set(
+        String::from_str(env, "drop"),
+        event_registry::TicketTier {
+            name: String::from_str(env, "Drop"),
+            price: 10000,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule: soroban_sdk::Vec::new(env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 1000,
+            sale_end: 2000,
+            price_schedule: soroban_sdk::Vec::new(env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: event_registry::AccessRule::Open,
+        },
+    );
+    tiers.set(
+        String::from_str(env, "scheduled"),
+        event_registry::TicketTier {
+            name: String::from_str(env, "Scheduled"),
+            price: 10000,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule,
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: soroban_sdk::Vec::new(env),
+            is_retired: false,
+            max_per_buyer: 0,
+            access_rule: event_registry::AccessRule::Open,
+        },
+    );
+    tiers.set(
+        String::from_str(env, "limited"),
+        event_registry::TicketTier {
+            name: String::from_str(env, "Limited"),
+            price: 10000,
+            tier_limit: 100,
+            current_sold: 0,
+            is_refundable: true,
+            refund_schedule: soroban_sdk::Vec::new(env),
+            max_resale_bps: 0,
+            royalty_bps: 0,
+            sale_start: 0,
+            sale_end: 0,
+            price_schedule: soroban_sdk::Vec::new(env),
+            is_retired: false,
+            max_per_buyer: 2,
+            access_rule: event_registry::AccessRule::Open,
+        },
+    );
+    tiers
+}
+
+// Resolves time-based price steps the way the registry does
+fn mock_current_price(env: &Env, tier_id: String) -> i128 {
+    let tier = mock_tiers(env).get(tier_id).unwrap();
+    let mut price = tier.price;
+    for step in tier.price_schedule.iter() {
+        if let event_registry::PriceTrigger::Time(at) = step.trigger {
+            if env.ledger().timestamp() >= at {
+                price = step.price;
+            }
+        }
+    }
+    price
+}
+
 // Mock Event Registry Contract
 #[soroban_sdk::contract]
 pub struct MockEventRegistry;
 
 #[soroban_sdk::contractimpl]
 impl MockEventRegistry {
+    pub fn is_paused(_env: Env) -> bool {
+        false
+    }
+
     pub fn get_event_payment_info(env: Env, _event_id: String) -> event_registry::PaymentInfo {
         event_registry::PaymentInfo {
             payment_address: Address::generate(&env),
             platform_fee_percent: 500, // 5%
+            tiers: mock_tiers(&env),
         }
     }
 
@@ -26,19 +221,50 @@ impl MockEventRegistry {
             organizer_address: Address::generate(&env),
             payment_address: Address::generate(&env),
             platform_fee_percent: 500,
-            is_active: true,
+            status: event_registry::EventStatus::Published,
             created_at: 0,
+            start_time: 0,
+            end_time: 0,
             metadata_cid: String::from_str(
                 &env,
                 "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
             ),
             max_supply: 0,
             current_supply: 0,
-            tiers: soroban_sdk::Map::new(&env),
+            max_per_buyer: 0,
+            tiers: mock_tiers(&env),
         })
     }
 
-    pub fn increment_inventory(_env: Env, _event_id: String, _tier_id: String) {}
+    pub fn get_current_price(env: Env, _event_id: String, tier_id: String) -> i128 {
+        mock_current_price(&env, tier_id)
+    }
+
+    // Every caller acts as the event owner
+    pub fn get_event_role(
+        _env: Env,
+        _event_id: String,
+        _account: Address,
+    ) -> Option<event_registry::EventRole> {
+        Some(event_registry::EventRole::Owner)
+    }
+
+    pub fn increment_inventory_bulk(
+        _env: Env,
+        _event_id: String,
+        _tier_id: String,
+        _buyer: Address,
+        _quantity: u32,
+    ) {
+    }
+
+    pub fn decrement_inventory_bulk(
+        _env: Env,
+        _event_id: String,
+        _tier_id: String,
+        _quantity: u32,
+    ) {
+    }
 }
 
 // Another Mock for different fee
@@ -47,10 +273,15 @@ pub struct MockEventRegistry2;
 
 #[soroban_sdk::contractimpl]
 impl MockEventRegistry2 {
+    pub fn is_paused(_env: Env) -> bool {
+        false
+    }
+
     pub fn get_event_payment_info(env: Env, _event_id: String) -> event_registry::PaymentInfo {
         event_registry::PaymentInfo {
             payment_address: Address::generate(&env),
             platform_fee_percent: 250, // 2.5%
+            tiers: mock_tiers(&env),
         }
     }
 
@@ -60,19 +291,33 @@ impl MockEventRegistry2 {
             organizer_address: Address::generate(&env),
             payment_address: Address::generate(&env),
             platform_fee_percent: 250,
-            is_active: true,
+            status: event_registry::EventStatus::Published,
             created_at: 0,
+            start_time: 0,
+            end_time: 0,
             metadata_cid: String::from_str(
                 &env,
                 "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
             ),
             max_supply: 0,
             current_supply: 0,
-            tiers: soroban_sdk::Map::new(&env),
+            max_per_buyer: 0,
+            tiers: mock_tiers(&env),
         })
     }
 
-    pub fn increment_inventory(_env: Env, _event_id: String, _tier_id: String) {}
+    pub fn get_current_price(env: Env, _event_id: String, tier_id: String) -> i128 {
+        mock_current_price(&env, tier_id)
+    }
+
+    pub fn increment_inventory_bulk(
+        _env: Env,
+        _event_id: String,
+        _tier_id: String,
+        _buyer: Address,
+        _quantity: u32,
+    ) {
+    }
 }
 
 // Mock Event Registry returning EventNotFound
@@ -89,7 +334,14 @@ impl MockEventRegistryNotFound {
         None
     }
 
-    pub fn increment_inventory(_env: Env, _event_id: String, _tier_id: String) {}
+    pub fn increment_inventory_bulk(
+        _env: Env,
+        _event_id: String,
+        _tier_id: String,
+        _buyer: Address,
+        _quantity: u32,
+    ) {
+    }
 }
 
 // Manually mapping the trap in Soroban tests is sometimes tricky if we just panic.
@@ -156,12 +408,21 @@ fn test_process_payment_success() {
     let event_id = String::from_str(&env, "event_1");
     let tier_id = String::from_str(&env, "tier_1");
 
-    let result_id =
-        client.process_payment(&payment_id, &event_id, &tier_id, &buyer, &usdc_id, &amount);
+    let result_id = client.process_payment(
+        &payment_id,
+        &event_id,
+        &tier_id,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+        &None,
+        &None,
+    );
     assert_eq!(result_id, payment_id);
 
     // Check escrow balances
-    let escrow_balance = client.get_event_escrow_balance(&event_id);
+    let escrow_balance = client.get_event_escrow_balance(&event_id, &usdc_id);
     let expected_fee = (amount * 500) / 10000;
     assert_eq!(escrow_balance.platform_fee, expected_fee);
     assert_eq!(escrow_balance.organizer_amount, amount - expected_fee);
@@ -169,6 +430,7 @@ fn test_process_payment_success() {
     // Check payment record
     let payment = client.get_payment_status(&payment_id).unwrap();
     assert_eq!(payment.amount, amount);
+    assert_eq!(payment.tier_price, amount);
     assert_eq!(payment.platform_fee, expected_fee);
     assert_eq!(payment.status, PaymentStatus::Pending);
 
@@ -214,14 +476,19 @@ fn test_confirm_payment() {
         payment_id: payment_id.clone(),
         event_id: String::from_str(&env, "e1"),
         buyer_address: buyer,
+        token_address: Address::generate(&env),
         ticket_tier_id: String::from_str(&env, "t1"),
+        tier_price: 100,
+        quantity: 1,
         amount: 100,
         platform_fee: 5,
         organizer_amount: 95,
+        refunded_amount: 0,
         status: PaymentStatus::Pending,
         transaction_hash: String::from_str(&env, ""),
         created_at: 100,
         confirmed_at: None,
+        promo_code_hash: None,
     };
 
     env.as_contract(&client.address, || {
@@ -253,6 +520,9 @@ fn test_process_payment_zero_amount() {
         &buyer,
         &usdc_id,
         &0,
+        &1,
+        &None,
+        &None,
     );
 }
 
@@ -284,6 +554,9 @@ fn test_fee_calculation_variants() {
         &buyer,
         &usdc_id,
         &10000i128,
+        &1,
+        &None,
+        &None,
     );
 
     let payment = client
@@ -320,6 +593,9 @@ fn test_process_payment_not_found() {
         &buyer,
         &usdc_id,
         &10000i128,
+        &1,
+        &None,
+        &None,
     );
     // Since panic inside get_event_payment_info cannot easily map to get_code() == 2 right now without explicit Error returning in the mock,
     // this might return a generic EventNotFound due to our fallback logic.
@@ -497,6 +773,9 @@ fn test_process_payment_with_non_whitelisted_token() {
         &buyer,
         &non_whitelisted_token,
         &10000i128,
+        &1,
+        &None,
+        &None,
     );
 
     assert_eq!(res, Err(Ok(TicketPaymentError::TokenNotWhitelisted)));
@@ -534,23 +813,43 @@ fn test_process_payment_with_multiple_tokens() {
         &buyer1,
         &usdc_id,
         &usdc_amount,
+        &1,
+        &None,
+        &None,
     );
 
     client.process_payment(
         &String::from_str(&env, "pay_xlm"),
         &String::from_str(&env, "event_1"),
-        &String::from_str(&env, "tier_1"),
+        &String::from_str(&env, "tier_2"),
         &buyer2,
         &xlm_id,
         &xlm_amount,
+        &1,
+        &None,
+        &None,
     );
 
-    // Check escrow balances instead of direct transfers
-    let escrow_balance = client.get_event_escrow_balance(&String::from_str(&env, "event_1"));
+    // Escrow is tracked separately for each token
+    let event_id = String::from_str(&env, "event_1");
     let expected_usdc_fee = (usdc_amount * 500) / 10000;
     let expected_xlm_fee = (xlm_amount * 500) / 10000;
-    let total_expected_fee = expected_usdc_fee + expected_xlm_fee;
-    assert_eq!(escrow_balance.platform_fee, total_expected_fee);
+
+    let usdc_balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(usdc_balance.platform_fee, expected_usdc_fee);
+    assert_eq!(
+        usdc_balance.organizer_amount,
+        usdc_amount - expected_usdc_fee
+    );
+
+    let xlm_balance = client.get_event_escrow_balance(&event_id, &xlm_id);
+    assert_eq!(xlm_balance.platform_fee, expected_xlm_fee);
+    assert_eq!(xlm_balance.organizer_amount, xlm_amount - expected_xlm_fee);
+
+    let balances = client.get_event_escrow_balances(&event_id);
+    assert_eq!(balances.len(), 2);
+    assert_eq!(balances.get(usdc_id.clone()).unwrap(), usdc_balance);
+    assert_eq!(balances.get(xlm_id.clone()).unwrap(), xlm_balance);
 
     let payment1 = client
         .get_payment_status(&String::from_str(&env, "pay_usdc"))
@@ -569,10 +868,15 @@ pub struct MockEventRegistryMaxSupply;
 
 #[soroban_sdk::contractimpl]
 impl MockEventRegistryMaxSupply {
+    pub fn is_paused(_env: Env) -> bool {
+        false
+    }
+
     pub fn get_event_payment_info(env: Env, _event_id: String) -> event_registry::PaymentInfo {
         event_registry::PaymentInfo {
             payment_address: Address::generate(&env),
             platform_fee_percent: 500,
+            tiers: mock_tiers(&env),
         }
     }
 
@@ -582,19 +886,33 @@ impl MockEventRegistryMaxSupply {
             organizer_address: Address::generate(&env),
             payment_address: Address::generate(&env),
             platform_fee_percent: 500,
-            is_active: true,
+            status: event_registry::EventStatus::Published,
             created_at: 0,
+            start_time: 0,
+            end_time: 0,
             metadata_cid: String::from_str(
                 &env,
                 "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
             ),
             max_supply: 100,
             current_supply: 100,
-            tiers: soroban_sdk::Map::new(&env),
+            max_per_buyer: 0,
+            tiers: mock_tiers(&env),
         })
     }
 
-    pub fn increment_inventory(_env: Env, _event_id: String, _tier_id: String) {}
+    pub fn get_current_price(env: Env, _event_id: String, tier_id: String) -> i128 {
+        mock_current_price(&env, tier_id)
+    }
+
+    pub fn increment_inventory_bulk(
+        _env: Env,
+        _event_id: String,
+        _tier_id: String,
+        _buyer: Address,
+        _quantity: u32,
+    ) {
+    }
 }
 
 #[test]
@@ -624,6 +942,9 @@ fn test_process_payment_max_supply_exceeded() {
         &buyer,
         &usdc_id,
         &10000i128,
+        &1,
+        &None,
+        &None,
     );
 
     assert_eq!(res, Err(Ok(TicketPaymentError::MaxSupplyExceeded)));
@@ -635,10 +956,15 @@ pub struct MockEventRegistryWithInventory;
 
 #[soroban_sdk::contractimpl]
 impl MockEventRegistryWithInventory {
+    pub fn is_paused(_env: Env) -> bool {
+        false
+    }
+
     pub fn get_event_payment_info(env: Env, _event_id: String) -> event_registry::PaymentInfo {
         event_registry::PaymentInfo {
             payment_address: Address::generate(&env),
             platform_fee_percent: 500,
+            tiers: mock_tiers(&env),
         }
     }
 
@@ -651,22 +977,37 @@ impl MockEventRegistryWithInventory {
             organizer_address: Address::generate(&env),
             payment_address: Address::generate(&env),
             platform_fee_percent: 500,
-            is_active: true,
+            status: event_registry::EventStatus::Published,
             created_at: 0,
+            start_time: 0,
+            end_time: 0,
             metadata_cid: String::from_str(
                 &env,
                 "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
             ),
             max_supply: 10,
             current_supply,
-            tiers: soroban_sdk::Map::new(&env),
+            max_per_buyer: 0,
+            tiers: mock_tiers(&env),
         })
     }
 
-    pub fn increment_inventory(env: Env, _event_id: String, _tier_id: String) {
+    pub fn get_current_price(env: Env, _event_id: String, tier_id: String) -> i128 {
+        mock_current_price(&env, tier_id)
+    }
+
+    pub fn increment_inventory_bulk(
+        env: Env,
+        _event_id: String,
+        _tier_id: String,
+        _buyer: Address,
+        quantity: u32,
+    ) {
         let key = Symbol::new(&env, "supply");
         let current: i128 = env.storage().instance().get(&key).unwrap_or(0);
-        env.storage().instance().set(&key, &(current + 1));
+        env.storage()
+            .instance()
+            .set(&key, &(current + quantity as i128));
     }
 }
 
@@ -700,6 +1041,9 @@ fn test_inventory_increment_on_successful_payment() {
         &buyer,
         &usdc_id,
         &amount,
+        &1,
+        &None,
+        &None,
     );
     assert_eq!(result1, String::from_str(&env, "pay_1"));
 
@@ -711,6 +1055,9 @@ fn test_inventory_increment_on_successful_payment() {
         &buyer,
         &usdc_id,
         &amount,
+        &1,
+        &None,
+        &None,
     );
     assert_eq!(result2, String::from_str(&env, "pay_2"));
 }
@@ -721,6 +1068,7 @@ fn test_withdraw_organizer_funds() {
     env.mock_all_auths();
 
     let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let organizer = Address::generate(&env);
     let usdc_token = token::StellarAssetClient::new(&env, &usdc_id);
 
     let buyer = Address::generate(&env);
@@ -738,15 +1086,18 @@ fn test_withdraw_organizer_funds() {
         &buyer,
         &usdc_id,
         &amount,
+        &1,
+        &None,
+        &None,
     );
 
-    let balance = client.get_event_escrow_balance(&event_id);
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
     assert!(balance.organizer_amount > 0);
 
-    let withdrawn = client.withdraw_organizer_funds(&event_id, &usdc_id);
+    let withdrawn = client.withdraw_organizer_funds(&organizer, &event_id, &usdc_id);
     assert_eq!(withdrawn, balance.organizer_amount);
 
-    let new_balance = client.get_event_escrow_balance(&event_id);
+    let new_balance = client.get_event_escrow_balance(&event_id, &usdc_id);
     assert_eq!(new_balance.organizer_amount, 0);
 }
 
@@ -773,9 +1124,12 @@ fn test_withdraw_platform_fees() {
         &buyer,
         &usdc_id,
         &amount,
+        &1,
+        &None,
+        &None,
     );
 
-    let balance = client.get_event_escrow_balance(&event_id);
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
     let initial_platform_balance = token::Client::new(&env, &usdc_id).balance(&platform_wallet);
 
     let withdrawn = client.withdraw_platform_fees(&event_id, &usdc_id);
@@ -787,6 +1141,1809 @@ fn test_withdraw_platform_fees() {
         balance.platform_fee
     );
 
-    let new_balance = client.get_event_escrow_balance(&event_id);
+    let new_balance = client.get_event_escrow_balance(&event_id, &usdc_id);
     assert_eq!(new_balance.platform_fee, 0);
 }
+
+#[test]
+fn test_process_payment_underpayment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let res = client.try_process_payment(
+        &String::from_str(&env, "p1"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::Underpayment)));
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&buyer), amount);
+}
+
+#[test]
+fn test_process_payment_overpayment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000001i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let res = client.try_process_payment(
+        &String::from_str(&env, "p1"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::Overpayment)));
+}
+
+#[test]
+fn test_process_payment_unknown_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+
+    let res = client.try_process_payment(
+        &String::from_str(&env, "p1"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "vip"),
+        &buyer,
+        &usdc_id,
+        &1000_0000000i128,
+        &1,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::TierNotFound)));
+}
+
+#[test]
+fn test_process_payment_multiple_quantity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketPaymentContract, ());
+    let client = TicketPaymentContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let usdc_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let platform_wallet = Address::generate(&env);
+    let registry_id = env.register(MockEventRegistryWithInventory, ());
+
+    client.initialize(&admin, &usdc_id, &platform_wallet, &registry_id);
+
+    let buyer = Address::generate(&env);
+    let amount = 3 * 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let event_id = String::from_str(&env, "event_1");
+    let payment_id = String::from_str(&env, "group_pay");
+    client.process_payment(
+        &payment_id,
+        &event_id,
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &usdc_id,
+        &amount,
+        &3,
+        &None,
+        &None,
+    );
+
+    let payment = client.get_payment_status(&payment_id).unwrap();
+    assert_eq!(payment.quantity, 3);
+    assert_eq!(payment.tier_price, 1000_0000000);
+    assert_eq!(payment.amount, amount);
+
+    let escrow_balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(
+        escrow_balance.organizer_amount + escrow_balance.platform_fee,
+        amount
+    );
+
+    let registry = event_registry::Client::new(&env, &registry_id);
+    assert_eq!(registry.get_event(&event_id).unwrap().current_supply, 3);
+}
+
+#[test]
+fn test_process_payment_quantity_exceeds_max_supply() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketPaymentContract, ());
+    let client = TicketPaymentContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let usdc_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let platform_wallet = Address::generate(&env);
+    let registry_id = env.register(MockEventRegistryWithInventory, ());
+
+    client.initialize(&admin, &usdc_id, &platform_wallet, &registry_id);
+
+    let buyer = Address::generate(&env);
+    let res = client.try_process_payment(
+        &String::from_str(&env, "p1"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "t1"),
+        &buyer,
+        &usdc_id,
+        &(11 * 10000i128),
+        &11,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::MaxSupplyExceeded)));
+}
+
+#[test]
+fn test_process_payment_quantity_underpayment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+
+    let res = client.try_process_payment(
+        &String::from_str(&env, "p1"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &usdc_id,
+        &1000_0000000i128,
+        &2,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::Underpayment)));
+}
+
+#[test]
+fn test_process_payment_zero_quantity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+
+    let res = client.try_process_payment(
+        &String::from_str(&env, "p1"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &usdc_id,
+        &1000_0000000i128,
+        &0,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidQuantity)));
+}
+
+fn pay_for_tier_1(
+    env: &Env,
+    client: &TicketPaymentContractClient,
+    usdc_id: &Address,
+    payment_id: &str,
+) -> (Address, i128) {
+    let buyer = Address::generate(env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(env, usdc_id).mint(&buyer, &amount);
+    token::Client::new(env, usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    client.process_payment(
+        &String::from_str(env, payment_id),
+        &String::from_str(env, "event_1"),
+        &String::from_str(env, "tier_1"),
+        &buyer,
+        usdc_id,
+        &amount,
+        &1,
+        &None,
+        &None,
+    );
+    (buyer, amount)
+}
+
+#[test]
+fn test_request_guest_refund_returns_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let (buyer, amount) = pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+    let usdc = token::Client::new(&env, &usdc_id);
+    assert_eq!(usdc.balance(&buyer), 0);
+
+    let payment_id = String::from_str(&env, "pay_1");
+    client.request_guest_refund(&payment_id);
+
+    assert_eq!(usdc.balance(&buyer), amount);
+    assert_eq!(usdc.balance(&client.address), 0);
+
+    let balance = client.get_event_escrow_balance(&String::from_str(&env, "event_1"), &usdc_id);
+    assert_eq!(balance.organizer_amount, 0);
+    assert_eq!(balance.platform_fee, 0);
+
+    let payment = client.get_payment_status(&payment_id).unwrap();
+    assert_eq!(payment.status, PaymentStatus::Refunded);
+
+    let res = client.try_request_guest_refund(&payment_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidPaymentStatus)));
+}
+
+#[test]
+fn test_request_guest_refund_after_organizer_withdrawal_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let organizer = Address::generate(&env);
+    let (buyer, _) = pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+
+    let event_id = String::from_str(&env, "event_1");
+    client.withdraw_organizer_funds(&organizer, &event_id, &usdc_id);
+
+    let res = client.try_request_guest_refund(&String::from_str(&env, "pay_1"));
+    assert_eq!(res, Err(Ok(TicketPaymentError::InsufficientEscrowBalance)));
+
+    // Nothing moved and the platform share is still held
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&buyer), 0);
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(balance.platform_fee, (1000_0000000i128 * 500) / 10000);
+}
+
+#[test]
+fn test_request_guest_refund_leaves_other_payments_in_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_2");
+
+    client.request_guest_refund(&String::from_str(&env, "pay_1"));
+
+    let balance = client.get_event_escrow_balance(&String::from_str(&env, "event_1"), &usdc_id);
+    let expected_fee = (1000_0000000i128 * 500) / 10000;
+    assert_eq!(balance.platform_fee, expected_fee);
+    assert_eq!(balance.organizer_amount, 1000_0000000i128 - expected_fee);
+    assert_eq!(
+        token::Client::new(&env, &usdc_id).balance(&client.address),
+        1000_0000000i128
+    );
+}
+
+// Mock Event Registry whose organizer and active flag are controlled by the test
+#[soroban_sdk::contract]
+pub struct MockEventRegistryCancellable;
+
+#[soroban_sdk::contractimpl]
+impl MockEventRegistryCancellable {
+    pub fn setup(env: Env, organizer: Address) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "organizer"), &organizer);
+        env.storage().instance().set(
+            &Symbol::new(&env, "status"),
+            &event_registry::EventStatus::Published,
+        );
+    }
+
+    pub fn set_status(env: Env, status: event_registry::EventStatus) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "status"), &status);
+    }
+
+    pub fn set_role(env: Env, account: Address, role: Option<event_registry::EventRole>) {
+        let key = (Symbol::new(&env, "role"), account);
+        match role {
+            Some(role) => env.storage().instance().set(&key, &role),
+            None => env.storage().instance().remove(&key),
+        }
+    }
+
+    pub fn get_event_role(
+        env: Env,
+        _event_id: String,
+        account: Address,
+    ) -> Option<event_registry::EventRole> {
+        let organizer: Address = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "organizer"))
+            .unwrap();
+        if account == organizer {
+            return Some(event_registry::EventRole::Owner);
+        }
+        env.storage()
+            .instance()
+            .get(&(Symbol::new(&env, "role"), account))
+    }
+
+    pub fn set_payment_address(env: Env, payment_address: Address) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "payment_address"), &payment_address);
+    }
+
+    pub fn set_start_time(env: Env, start_time: u64) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "start_time"), &start_time);
+    }
+
+    pub fn set_end_time(env: Env, end_time: u64) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "end_time"), &end_time);
+    }
+
+    pub fn redeem_promo_code(
+        env: Env,
+        _event_id: String,
+        _tier_id: String,
+        code: soroban_sdk::Bytes,
+        quantity: u32,
+    ) -> event_registry::PromoCode {
+        if code != soroban_sdk::Bytes::from_slice(&env, b"SAVE10") {
+            panic!("unknown promo code");
+        }
+        let redemptions = Self::promo_redemptions(env.clone()) + quantity;
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "redemptions"), &redemptions);
+        event_registry::PromoCode {
+            code_hash: env.crypto().sha256(&code).into(),
+            discount: event_registry::Discount::Percentage(1000),
+            tiers: soroban_sdk::Vec::new(&env),
+            max_redemptions: 0,
+            redemptions,
+            expires_at: 0,
+        }
+    }
+
+    pub fn credit_promo_code(
+        env: Env,
+        _event_id: String,
+        _code_hash: soroban_sdk::BytesN<32>,
+        quantity: u32,
+    ) {
+        let redemptions = Self::promo_redemptions(env.clone()).saturating_sub(quantity);
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "redemptions"), &redemptions);
+    }
+
+    pub fn set_paused(env: Env, paused: bool) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "paused"), &paused);
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "paused"))
+            .unwrap_or(false)
+    }
+
+    pub fn promo_redemptions(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "redemptions"))
+            .unwrap_or(0)
+    }
+
+    pub fn set_access_rule(env: Env, rule: event_registry::AccessRule) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "access_rule"), &rule);
+    }
+
+    pub fn allow(env: Env, account: Address) {
+        env.storage()
+            .instance()
+            .set(&(Symbol::new(&env, "allowed"), account), &true);
+    }
+
+    pub fn is_allowlisted(env: Env, _event_id: String, _tier_id: String, account: Address) -> bool {
+        env.storage()
+            .instance()
+            .has(&(Symbol::new(&env, "allowed"), account))
+    }
+
+    pub fn set_max_per_buyer(env: Env, max_per_buyer: u32) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "max_per_buyer"), &max_per_buyer);
+    }
+
+    pub fn set_platform_fee(env: Env, fee_percent: u32) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "fee"), &fee_percent);
+    }
+
+    pub fn get_event_payment_info(env: Env, _event_id: String) -> event_registry::PaymentInfo {
+        let organizer: Address = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "organizer"))
+            .unwrap();
+        event_registry::PaymentInfo {
+            payment_address: organizer,
+            platform_fee_percent: env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "fee"))
+                .unwrap_or(500),
+            tiers: mock_tiers(&env),
+        }
+    }
+
+    pub fn get_event(env: Env, event_id: String) -> Option<event_registry::EventInfo> {
+        // The "t1" tier takes the access rule set by the test, if any
+        let mut tiers = mock_tiers(&env);
+        if let Some(rule) = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "access_rule"))
+        {
+            let tier_id = String::from_str(&env, "t1");
+            let mut tier = tiers.get(tier_id.clone()).unwrap();
+            tier.access_rule = rule;
+            tiers.set(tier_id, tier);
+        }
+        let organizer: Address = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "organizer"))
+            .unwrap();
+        let status: event_registry::EventStatus = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "status"))
+            .unwrap();
+
+        Some(event_registry::EventInfo {
+            event_id,
+            organizer_address: organizer.clone(),
+            payment_address: env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "payment_address"))
+                .unwrap_or(organizer),
+            platform_fee_percent: 500,
+            status,
+            created_at: 0,
+            start_time: env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "start_time"))
+                .unwrap_or(0),
+            end_time: env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "end_time"))
+                .unwrap_or(0),
+            metadata_cid: String::from_str(
+                &env,
+                "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            ),
+            max_supply: 0,
+            current_supply: 0,
+            max_per_buyer: env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "max_per_buyer"))
+                .unwrap_or(0),
+            tiers,
+        })
+    }
+
+    pub fn get_current_price(env: Env, _event_id: String, tier_id: String) -> i128 {
+        mock_current_price(&env, tier_id)
+    }
+
+    pub fn increment_inventory_bulk(
+        _env: Env,
+        _event_id: String,
+        _tier_id: String,
+        _buyer: Address,
+        _quantity: u32,
+    ) {
+    }
+
+    pub fn decrement_inventory_bulk(
+        _env: Env,
+        _event_id: String,
+        _tier_id: String,
+        _quantity: u32,
+    ) {
+    }
+}
+
+fn setup_cancellable(
+    env: &Env,
+) -> (
+    TicketPaymentContractClient<'static>,
+    Address,
+    Address,
+    Address,
+    MockEventRegistryCancellableClient<'static>,
+) {
+    let contract_id = env.register(TicketPaymentContract, ());
+    let client = TicketPaymentContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let organizer = Address::generate(env);
+    let usdc_id = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    let registry_id = env.register(MockEventRegistryCancellable, ());
+    let registry = MockEventRegistryCancellableClient::new(env, &registry_id);
+    registry.setup(&organizer);
+
+    client.initialize(&admin, &usdc_id, &Address::generate(env), &registry_id);
+
+    (client, admin, organizer, usdc_id, registry)
+}
+
+#[test]
+fn test_refund_cancelled_event_in_batches() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, organizer, usdc_id, registry) = setup_cancellable(&env);
+    let usdc = token::Client::new(&env, &usdc_id);
+
+    let ids = ["p0", "p1", "p2", "p3", "p4"];
+    let mut buyers = soroban_sdk::Vec::new(&env);
+    for id in ids {
+        let (buyer, _) = pay_for_tier_1(&env, &client, &usdc_id, id);
+        buyers.push_back(buyer);
+    }
+    // Confirming a payment must not duplicate it in the event index
+    client.confirm_payment(&String::from_str(&env, "p0"), &String::from_str(&env, "tx"));
+
+    registry.set_status(&event_registry::EventStatus::Cancelled);
+    let event_id = String::from_str(&env, "event_1");
+
+    let first = client.refund_cancelled_event(&organizer, &event_id, &2);
+    assert_eq!(first.processed, 2);
+    assert_eq!(first.refunded, 2);
+    assert_eq!(first.next_cursor, 2);
+    assert_eq!(first.total_payments, 5);
+    assert!(!first.completed);
+    assert_eq!(client.get_refund_cursor(&event_id), 2);
+
+    let second = client.refund_cancelled_event(&organizer, &event_id, &2);
+    assert_eq!(second.refunded, 2);
+    assert!(!second.completed);
+
+    let third = client.refund_cancelled_event(&organizer, &event_id, &2);
+    assert_eq!(third.processed, 1);
+    assert_eq!(third.refunded, 1);
+    assert!(third.completed);
+
+    for buyer in buyers.iter() {
+        assert_eq!(usdc.balance(&buyer), 1000_0000000i128);
+    }
+    assert_eq!(usdc.balance(&client.address), 0);
+    for id in ids {
+        let payment = client
+            .get_payment_status(&String::from_str(&env, id))
+            .unwrap();
+        assert_eq!(payment.status, PaymentStatus::Refunded);
+    }
+
+    // Running again after completion is a no-op
+    let done = client.refund_cancelled_event(&organizer, &event_id, &2);
+    assert_eq!(done.processed, 0);
+    assert!(done.completed);
+}
+
+#[test]
+fn test_refund_cancelled_event_by_admin_skips_refunded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _organizer, usdc_id, registry) = setup_cancellable(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "p0");
+    pay_for_tier_1(&env, &client, &usdc_id, "p1");
+    client.request_guest_refund(&String::from_str(&env, "p0"));
+
+    registry.set_status(&event_registry::EventStatus::Cancelled);
+    let result = client.refund_cancelled_event(&admin, &String::from_str(&env, "event_1"), &10);
+    assert_eq!(result.processed, 2);
+    assert_eq!(result.refunded, 1);
+    assert_eq!(result.amount_refunded, 1000_0000000i128);
+    assert!(result.completed);
+}
+
+#[test]
+fn test_refund_cancelled_event_requires_cancellation_and_role() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, organizer, usdc_id, registry) = setup_cancellable(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "p0");
+    let event_id = String::from_str(&env, "event_1");
+
+    let res = client.try_refund_cancelled_event(&organizer, &event_id, &10);
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventNotCancelled)));
+
+    registry.set_status(&event_registry::EventStatus::Cancelled);
+
+    let stranger = Address::generate(&env);
+    let res = client.try_refund_cancelled_event(&stranger, &event_id, &10);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    let res = client.try_refund_cancelled_event(&organizer, &event_id, &0);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidBatchSize)));
+}
+
+fn pay_for_scheduled_tier(
+    env: &Env,
+    client: &TicketPaymentContractClient,
+    usdc_id: &Address,
+) -> Address {
+    let buyer = Address::generate(env);
+    token::StellarAssetClient::new(env, usdc_id).mint(&buyer, &10000);
+    token::Client::new(env, usdc_id).approve(&buyer, &client.address, &10000, &99999);
+    client.process_payment(
+        &String::from_str(env, "p_sched"),
+        &String::from_str(env, "event_1"),
+        &String::from_str(env, "scheduled"),
+        &buyer,
+        usdc_id,
+        &10000,
+        &1,
+        &None,
+        &None,
+    );
+    buyer
+}
+
+#[test]
+fn test_refund_schedule_full_before_first_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(500);
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = pay_for_scheduled_tier(&env, &client, &usdc_id);
+
+    client.request_guest_refund(&String::from_str(&env, "p_sched"));
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&buyer), 10000);
+}
+
+#[test]
+fn test_refund_schedule_partial_refund_splits_kept_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(500);
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = pay_for_scheduled_tier(&env, &client, &usdc_id);
+
+    env.ledger().set_timestamp(1500);
+    let payment_id = String::from_str(&env, "p_sched");
+    client.request_guest_refund(&payment_id);
+
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&buyer), 5000);
+
+    // 5% fee: 9500 organizer / 500 platform, half of each is kept
+    let balance = client.get_event_escrow_balance(&String::from_str(&env, "event_1"), &usdc_id);
+    assert_eq!(balance.organizer_amount, 4750);
+    assert_eq!(balance.platform_fee, 250);
+
+    let payment = client.get_payment_status(&payment_id).unwrap();
+    assert_eq!(payment.status, PaymentStatus::Refunded);
+    assert_eq!(payment.refunded_amount, 5000);
+}
+
+#[test]
+fn test_refund_schedule_after_last_deadline_not_refundable() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(500);
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    pay_for_scheduled_tier(&env, &client, &usdc_id);
+
+    env.ledger().set_timestamp(2001);
+    let res = client.try_request_guest_refund(&String::from_str(&env, "p_sched"));
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketNotRefundable)));
+}
+
+#[test]
+fn test_refund_schedule_ignored_for_cancelled_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(500);
+
+    let (client, _admin, _organizer, usdc_id, registry) = setup_cancellable(&env);
+    let buyer = pay_for_scheduled_tier(&env, &client, &usdc_id);
+
+    env.ledger().set_timestamp(5000);
+    registry.set_status(&event_registry::EventStatus::Cancelled);
+    client.request_guest_refund(&String::from_str(&env, "p_sched"));
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&buyer), 10000);
+}
+
+#[test]
+fn test_withdraw_organizer_funds_per_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let organizer = Address::generate(&env);
+    let xlm_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_token(&xlm_id);
+
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_usdc");
+
+    let event_id = String::from_str(&env, "event_1");
+    let usdc_balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+
+    // Nothing was collected in XLM, so nothing can be withdrawn in XLM
+    assert_eq!(
+        client.withdraw_organizer_funds(&organizer, &event_id, &xlm_id),
+        0
+    );
+    assert_eq!(
+        client.get_event_escrow_balance(&event_id, &usdc_id),
+        usdc_balance
+    );
+
+    let withdrawn = client.withdraw_organizer_funds(&organizer, &event_id, &usdc_id);
+    assert_eq!(withdrawn, usdc_balance.organizer_amount);
+    assert_eq!(
+        client
+            .get_event_escrow_balance(&event_id, &usdc_id)
+            .organizer_amount,
+        0
+    );
+}
+
+#[test]
+fn test_withdraw_organizer_funds_locked_until_end_plus_hold() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, organizer, usdc_id, registry) = setup_cancellable(&env);
+    registry.set_end_time(&1000);
+    client.set_release_hold_period(&500);
+    assert_eq!(client.get_release_hold_period(), 500);
+
+    pay_for_tier_1(&env, &client, &usdc_id, "p0");
+    let event_id = String::from_str(&env, "event_1");
+    assert_eq!(client.get_funds_unlock_time(&event_id), 1500);
+
+    env.ledger().set_timestamp(1200);
+    let res = client.try_withdraw_organizer_funds(&organizer, &event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::FundsLocked)));
+
+    env.ledger().set_timestamp(1500);
+    let expected = 1000_0000000i128 - (1000_0000000i128 * 500) / 10000;
+    assert_eq!(
+        client.withdraw_organizer_funds(&organizer, &event_id, &usdc_id),
+        expected
+    );
+    assert_eq!(
+        token::Client::new(&env, &usdc_id).balance(&organizer),
+        expected
+    );
+}
+
+#[test]
+fn test_trusted_organizer_withdraws_before_unlock() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, organizer, usdc_id, registry) = setup_cancellable(&env);
+    registry.set_end_time(&1000);
+    client.set_release_hold_period(&500);
+    client.set_trusted_organizer(&organizer, &true);
+    assert!(client.is_trusted_organizer(&organizer));
+
+    pay_for_tier_1(&env, &client, &usdc_id, "p0");
+    let event_id = String::from_str(&env, "event_1");
+    assert_eq!(client.get_funds_unlock_time(&event_id), 0);
+
+    env.ledger().set_timestamp(100);
+    assert!(client.withdraw_organizer_funds(&organizer, &event_id, &usdc_id) > 0);
+
+    client.set_trusted_organizer(&organizer, &false);
+    assert!(!client.is_trusted_organizer(&organizer));
+    assert_eq!(client.get_funds_unlock_time(&event_id), 1500);
+}
+
+#[test]
+fn test_cancelled_event_funds_stay_in_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, organizer, usdc_id, registry) = setup_cancellable(&env);
+    client.set_trusted_organizer(&organizer, &true);
+    let (buyer, amount) = pay_for_tier_1(&env, &client, &usdc_id, "p0");
+    let event_id = String::from_str(&env, "event_1");
+
+    registry.set_status(&event_registry::EventStatus::Cancelled);
+    let res = client.try_withdraw_organizer_funds(&organizer, &event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventCancelled)));
+
+    // The escrow is still there to refund the buyer in full
+    let result = client.refund_cancelled_event(&organizer, &event_id, &10);
+    assert!(result.completed);
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&buyer), amount);
+}
+
+#[test]
+fn test_no_guest_refund_after_event_completed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _organizer, usdc_id, registry) = setup_cancellable(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "p0");
+
+    registry.set_status(&event_registry::EventStatus::Completed);
+    let res = client.try_request_guest_refund(&String::from_str(&env, "p0"));
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventInactive)));
+}
+
+#[test]
+fn test_platform_fees_stay_in_escrow_for_refunds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _organizer, usdc_id, registry) = setup_cancellable(&env);
+    registry.set_end_time(&1000);
+    let (buyer, amount) = pay_for_tier_1(&env, &client, &usdc_id, "p0");
+    pay_for_tier_1(&env, &client, &usdc_id, "p1");
+    let event_id = String::from_str(&env, "event_1");
+
+    // Fees are locked like organizer funds, so a refund can still be paid in full
+    let res = client.try_withdraw_platform_fees(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::FundsLocked)));
+    client.request_guest_refund(&String::from_str(&env, "p0"));
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&buyer), amount);
+
+    // ...and are never released for a cancelled event
+    registry.set_status(&event_registry::EventStatus::Cancelled);
+    env.ledger().set_timestamp(1000);
+    let res = client.try_withdraw_platform_fees(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventCancelled)));
+}
+
+#[test]
+fn test_process_payment_issues_tickets() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    let amount = 3 * 10000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let payment_id = String::from_str(&env, "group");
+    client.process_payment(
+        &payment_id,
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "t1"),
+        &buyer,
+        &usdc_id,
+        &amount,
+        &3,
+        &None,
+        &None,
+    );
+
+    let ticket_ids = client.tickets_of(&buyer);
+    assert_eq!(ticket_ids, soroban_sdk::vec![&env, 1u64, 2u64, 3u64]);
+    assert_eq!(client.get_payment_tickets(&payment_id), ticket_ids);
+
+    let ticket = client.get_ticket(&2).unwrap();
+    assert_eq!(ticket.owner, buyer);
+    assert_eq!(ticket.payment_id, payment_id);
+    assert_eq!(ticket.ticket_tier_id, String::from_str(&env, "t1"));
+    assert_eq!(ticket.status, TicketStatus::Active);
+    assert_eq!(client.owner_of(&3), buyer);
+}
+
+#[test]
+fn test_process_payment_duplicate_id_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+
+    let buyer = Address::generate(&env);
+    let res = client.try_process_payment(
+        &String::from_str(&env, "pay_1"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &usdc_id,
+        &1000_0000000i128,
+        &1,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::PaymentAlreadyExists)));
+}
+
+#[test]
+fn test_transfer_ticket() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let (buyer, _) = pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+    let friend = Address::generate(&env);
+
+    client.transfer_ticket(&1, &friend);
+
+    assert_eq!(client.owner_of(&1), friend);
+    assert_eq!(client.tickets_of(&buyer).len(), 0);
+    assert_eq!(client.tickets_of(&friend), soroban_sdk::vec![&env, 1u64]);
+
+    // The buyer gave the ticket away, so they cannot refund it anymore
+    let res = client.try_request_guest_refund(&String::from_str(&env, "pay_1"));
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketTransferred)));
+
+    let res = client.try_transfer_ticket(&1, &friend);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidAddress)));
+    let res = client.try_transfer_ticket(&99, &buyer);
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketNotFound)));
+}
+
+#[test]
+fn test_refund_voids_tickets() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let (buyer, _) = pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+
+    client.request_guest_refund(&String::from_str(&env, "pay_1"));
+
+    assert_eq!(client.tickets_of(&buyer).len(), 0);
+    assert_eq!(
+        client.get_ticket(&1).unwrap().status,
+        TicketStatus::Refunded
+    );
+
+    let res = client.try_transfer_ticket(&1, &Address::generate(&env));
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketNotActive)));
+}
+
+#[test]
+fn test_check_in_ticket_by_organizer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(700);
+
+    let (client, _admin, organizer, usdc_id, _registry) = setup_cancellable(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+
+    client.check_in_ticket(&organizer, &1);
+
+    let ticket = client.get_ticket(&1).unwrap();
+    assert_eq!(ticket.status, TicketStatus::CheckedIn);
+    assert_eq!(ticket.checked_in_at, Some(700));
+
+    let res = client.try_check_in_ticket(&organizer, &1);
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketAlreadyCheckedIn)));
+
+    // Used tickets can be neither refunded nor transferred
+    let res = client.try_request_guest_refund(&String::from_str(&env, "pay_1"));
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketAlreadyCheckedIn)));
+    let res = client.try_transfer_ticket(&1, &Address::generate(&env));
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketNotActive)));
+}
+
+#[test]
+fn test_check_in_ticket_by_scanner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _organizer, usdc_id, registry) = setup_cancellable(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_2");
+    let scanner = Address::generate(&env);
+
+    let res = client.try_check_in_ticket(&scanner, &1);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    registry.set_role(&scanner, &Some(event_registry::EventRole::Scanner));
+    client.check_in_ticket(&scanner, &1);
+    assert_eq!(
+        client.get_ticket(&1).unwrap().status,
+        TicketStatus::CheckedIn
+    );
+
+    registry.set_role(&scanner, &None);
+    let res = client.try_check_in_ticket(&scanner, &2);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+}
+
+#[test]
+fn test_check_in_refunded_ticket_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, organizer, usdc_id, _registry) = setup_cancellable(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+    client.request_guest_refund(&String::from_str(&env, "pay_1"));
+
+    let res = client.try_check_in_ticket(&organizer, &1);
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketRefunded)));
+}
+
+#[test]
+fn test_resale_listing_and_purchase() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, platform_wallet, _) = setup_test(&env);
+    let (seller, _) = pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+    let usdc = token::Client::new(&env, &usdc_id);
+    let platform_before = usdc.balance(&platform_wallet);
+
+    let price = 1100_0000000i128;
+    client.list_ticket(&1, &price, &usdc_id);
+    let listing = client.get_listing(&1).unwrap();
+    assert_eq!(listing.seller, seller);
+    assert_eq!(listing.price, price);
+
+    // Listed tickets cannot be transferred or refunded
+    let res = client.try_transfer_ticket(&1, &Address::generate(&env));
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketListed)));
+    let res = client.try_request_guest_refund(&String::from_str(&env, "pay_1"));
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketListed)));
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &price);
+    usdc.approve(&buyer, &client.address, &price, &99999);
+    client.buy_listing(&1, &buyer);
+
+    // 5% platform fee, 10% royalty, the rest to the seller
+    assert_eq!(usdc.balance(&buyer), 0);
+    assert_eq!(usdc.balance(&platform_wallet) - platform_before, 55_0000000);
+    assert_eq!(usdc.balance(&seller), 935_0000000);
+
+    assert_eq!(client.owner_of(&1), buyer);
+    assert_eq!(client.tickets_of(&seller).len(), 0);
+    assert_eq!(client.tickets_of(&buyer), soroban_sdk::vec![&env, 1u64]);
+    assert_eq!(client.get_listing(&1), None);
+
+    let res = client.try_buy_listing(&1, &buyer);
+    assert_eq!(res, Err(Ok(TicketPaymentError::ListingNotFound)));
+}
+
+#[test]
+fn test_resale_price_cap_and_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+
+    // Above 120% of the 1000 USDC face value
+    let res = client.try_list_ticket(&1, &1200_0000001, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidResalePrice)));
+    let res = client.try_list_ticket(&1, &0, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidResalePrice)));
+    client.list_ticket(&1, &1200_0000000, &usdc_id);
+
+    // Tier without a resale policy
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &500_0000000);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &500_0000000, &99999);
+    client.process_payment(
+        &String::from_str(&env, "pay_2"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_2"),
+        &buyer,
+        &usdc_id,
+        &500_0000000,
+        &1,
+        &None,
+        &None,
+    );
+    let res = client.try_list_ticket(&2, &500_0000000, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::ResaleNotAllowed)));
+}
+
+#[test]
+fn test_cancel_listing() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+
+    client.list_ticket(&1, &1000_0000000, &usdc_id);
+    client.cancel_listing(&1);
+    assert_eq!(client.get_listing(&1), None);
+
+    let res = client.try_cancel_listing(&1);
+    assert_eq!(res, Err(Ok(TicketPaymentError::ListingNotFound)));
+
+    // Back to a plain ticket that can be given away
+    let friend = Address::generate(&env);
+    client.transfer_ticket(&1, &friend);
+    assert_eq!(client.owner_of(&1), friend);
+}
+
+#[test]
+fn test_process_payment_respects_sales_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &30000);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &30000, &99999);
+
+    let pay = |payment_id: &str| {
+        client.try_process_payment(
+            &String::from_str(&env, payment_id),
+            &String::from_str(&env, "event_1"),
+            &String::from_str(&env, "drop"),
+            &buyer,
+            &usdc_id,
+            &10000,
+            &1,
+            &None,
+            &None,
+        )
+    };
+
+    assert_eq!(pay("p0"), Err(Ok(TicketPaymentError::SalesNotStarted)));
+
+    env.ledger().set_timestamp(1000);
+    assert!(pay("p1").is_ok());
+
+    env.ledger().set_timestamp(2000);
+    assert_eq!(pay("p2"), Err(Ok(TicketPaymentError::SalesEnded)));
+}
+
+#[test]
+fn test_process_payment_rejected_after_event_end() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _organizer, usdc_id, registry) = setup_cancellable(&env);
+    registry.set_end_time(&1000);
+    env.ledger().set_timestamp(1000);
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let res = client.try_process_payment(
+        &String::from_str(&env, "p0"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::SalesEnded)));
+}
+
+#[test]
+fn test_process_payment_closes_at_event_start_by_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _organizer, usdc_id, registry) = setup_cancellable(&env);
+    registry.set_start_time(&1000);
+    env.ledger().set_timestamp(1000);
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    // tier_1 has no sale end of its own, so sales stop once doors open
+    let res = client.try_process_payment(
+        &String::from_str(&env, "p0"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::SalesEnded)));
+}
+
+#[test]
+fn test_process_payment_uses_current_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &25000);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &25000, &99999);
+
+    let pay = |payment_id: &str, amount: i128| {
+        client.try_process_payment(
+            &String::from_str(&env, payment_id),
+            &String::from_str(&env, "event_1"),
+            &String::from_str(&env, "early_bird"),
+            &buyer,
+            &usdc_id,
+            &amount,
+            &1,
+            &None,
+            &None,
+        )
+    };
+
+    assert!(pay("p0", 10000).is_ok());
+    let payment = client
+        .get_payment_status(&String::from_str(&env, "p0"))
+        .unwrap();
+    assert_eq!(payment.tier_price, 10000);
+
+    // The early-bird price is over, the old price no longer covers the ticket
+    env.ledger().set_timestamp(1000);
+    assert_eq!(pay("p1", 10000), Err(Ok(TicketPaymentError::Underpayment)));
+    assert!(pay("p2", 15000).is_ok());
+}
+
+#[test]
+fn test_process_payment_rejects_batch_crossing_price_step() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &60000);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &60000, &99999);
+
+    let pay = |payment_id: &str, quantity: u32| {
+        client.try_process_payment(
+            &String::from_str(&env, payment_id),
+            &String::from_str(&env, "event_1"),
+            &String::from_str(&env, "early_bird"),
+            &buyer,
+            &usdc_id,
+            &(10000 * quantity as i128),
+            &quantity,
+            &None,
+            &None,
+        )
+    };
+
+    // The price rises after the fifth ticket, so six cannot all go at the early price
+    assert_eq!(pay("p0", 6), Err(Ok(TicketPaymentError::PriceStepCrossed)));
+    assert!(pay("p1", 5).is_ok());
+}
+
+#[test]
+fn test_process_payment_retired_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &10000);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &10000, &99999);
+
+    let res = client.try_process_payment(
+        &String::from_str(&env, "p0"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "retired"),
+        &buyer,
+        &usdc_id,
+        &10000,
+        &1,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::TierRetired)));
+}
+
+#[test]
+fn test_process_payment_rejected_while_sales_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, organizer, usdc_id, registry) = setup_cancellable(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "p0");
+    registry.set_status(&event_registry::EventStatus::SalesPaused);
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+    let res = client.try_process_payment(
+        &String::from_str(&env, "p1"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventInactive)));
+
+    // Paused is not cancelled: no bulk refunds
+    let res =
+        client.try_refund_cancelled_event(&organizer, &String::from_str(&env, "event_1"), &10);
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventNotCancelled)));
+}
+
+#[test]
+fn test_withdraw_organizer_funds_requires_manager_role() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, organizer, usdc_id, registry) = setup_cancellable(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+    let event_id = String::from_str(&env, "event_1");
+    let scanner = Address::generate(&env);
+    let manager = Address::generate(&env);
+    registry.set_role(&scanner, &Some(event_registry::EventRole::Scanner));
+    registry.set_role(&manager, &Some(event_registry::EventRole::Manager));
+
+    let res = client.try_withdraw_organizer_funds(&scanner, &event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    // Funds still go to the organizer when a manager withdraws
+    let withdrawn = client.withdraw_organizer_funds(&manager, &event_id, &usdc_id);
+    assert!(withdrawn > 0);
+    assert_eq!(
+        token::Client::new(&env, &usdc_id).balance(&organizer),
+        withdrawn
+    );
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&manager), 0);
+}
+
+#[test]
+fn test_withdrawal_follows_event_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, organizer, usdc_id, registry) = setup_cancellable(&env);
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+    let event_id = String::from_str(&env, "event_1");
+
+    // The event changes hands with a new payment address
+    let new_organizer = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    registry.setup(&new_organizer);
+    registry.set_payment_address(&new_wallet);
+
+    let res = client.try_withdraw_organizer_funds(&organizer, &event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    let withdrawn = client.withdraw_organizer_funds(&new_organizer, &event_id, &usdc_id);
+    let usdc = token::Client::new(&env, &usdc_id);
+    assert_eq!(usdc.balance(&new_wallet), withdrawn);
+    assert_eq!(usdc.balance(&organizer), 0);
+}
+
+#[test]
+fn test_admin_transfer_and_platform_wallet() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, usdc_id, platform_wallet, _) = setup_test(&env);
+    assert_eq!(client.get_admin(), Some(admin.clone()));
+
+    let res = client.try_accept_admin();
+    assert_eq!(res, Err(Ok(TicketPaymentError::NoPendingAdmin)));
+
+    let new_admin = Address::generate(&env);
+    client.propose_admin(&new_admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_admin(), Some(admin));
+    client.accept_admin();
+    assert_eq!(client.get_admin(), Some(new_admin));
+    assert_eq!(client.get_pending_admin(), None);
+
+    // New fees go to the rotated wallet
+    let new_wallet = Address::generate(&env);
+    client.set_platform_wallet(&new_wallet);
+    assert_eq!(client.get_platform_wallet(), new_wallet);
+
+    pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+    let event_id = String::from_str(&env, "event_1");
+    let fees = client.withdraw_platform_fees(&event_id, &usdc_id);
+    let usdc = token::Client::new(&env, &usdc_id);
+    assert_eq!(usdc.balance(&new_wallet), fees);
+    assert_eq!(usdc.balance(&platform_wallet), 0);
+}
+
+#[test]
+fn test_pause_blocks_payments_and_withdrawals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, organizer, usdc_id, _registry) = setup_cancellable(&env);
+    let (buyer, amount) = pay_for_tier_1(&env, &client, &usdc_id, "pay_1");
+    let event_id = String::from_str(&env, "event_1");
+
+    client.pause(&7);
+    assert!(client.is_paused());
+
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+    let res = client.try_process_payment(
+        &String::from_str(&env, "pay_2"),
+        &event_id,
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::ContractPaused)));
+    let res = client.try_withdraw_organizer_funds(&organizer, &event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::ContractPaused)));
+    let res = client.try_withdraw_platform_fees(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::ContractPaused)));
+
+    // Refunds and queries keep working
+    client.request_guest_refund(&String::from_str(&env, "pay_1"));
+    assert_eq!(
+        client
+            .get_payment_status(&String::from_str(&env, "pay_1"))
+            .unwrap()
+            .status,
+        PaymentStatus::Refunded
+    );
+
+    client.unpause(&0);
+    assert!(!client.is_paused());
+}
+
+#[test]
+fn test_process_payment_rejected_while_registry_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _organizer, usdc_id, registry) = setup_cancellable(&env);
+    registry.set_paused(&true);
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &1000_0000000i128);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &1000_0000000i128, &99999);
+    let res = client.try_process_payment(
+        &String::from_str(&env, "pay_1"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &usdc_id,
+        &1000_0000000i128,
+        &1,
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::ContractPaused)));
+    assert_eq!(
+        token::Client::new(&env, &usdc_id).balance(&buyer),
+        1000_0000000i128
+    );
+}
+
+#[test]
+fn test_process_payment_uses_fee_override_from_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _organizer, usdc_id, registry) = setup_cancellable(&env);
+    let event_id = String::from_str(&env, "event_1");
+
+    // A fee override resolved by the registry wins over the fee stored on the event
+    registry.set_platform_fee(&100);
+    let (_, amount) = pay_for_tier_1(&env, &client, &usdc_id, "p0");
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(balance.platform_fee, amount / 100);
+
+    registry.set_platform_fee(&0);
+    pay_for_tier_1(&env, &client, &usdc_id, "p1");
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(balance.platform_fee, amount / 100);
+    assert_eq!(balance.organizer_amount, amount * 2 - amount / 100);
+}
+
+#[test]
+fn test_process_payment_with_promo_code() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _organizer, usdc_id, registry) = setup_cancellable(&env);
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &2000_0000000i128);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &2000_0000000i128, &99999);
+
+    let code = soroban_sdk::Bytes::from_slice(&env, b"SAVE10");
+    let pay = |id: &str, amount: i128, code: &Option<soroban_sdk::Bytes>| {
+        client.try_process_payment(
+            &String::from_str(&env, id),
+            &String::from_str(&env, "event_1"),
+            &String::from_str(&env, "tier_1"),
+            &buyer,
+            &usdc_id,
+            &amount,
+            &2,
+            code,
+            &None,
+        )
+    };
+
+    // The discount is taken off each ticket, so the full price is now an overpayment
+    let result = pay("p0", 2000_0000000, &Some(code.clone()));
+    assert_eq!(result, Err(Ok(TicketPaymentError::Overpayment)));
+    let result = pay(
+        "p0",
+        900_0000000,
+        &Some(soroban_sdk::Bytes::from_slice(&env, b"NOPE")),
+    );
+    assert_eq!(result, Err(Ok(TicketPaymentError::InvalidPromoCode)));
+
+    pay("p0", 1800_0000000, &Some(code.clone()))
+        .unwrap()
+        .unwrap();
+    let payment = client
+        .get_payment_status(&String::from_str(&env, "p0"))
+        .unwrap();
+    assert_eq!(payment.amount, 1800_0000000);
+    assert_eq!(payment.tier_price, 1000_0000000);
+    let code_hash: soroban_sdk::BytesN<32> = env.crypto().sha256(&code).into();
+    assert_eq!(payment.promo_code_hash, Some(code_hash));
+
+    // Each ticket counts as a redemption and a refund gives them back
+    assert_eq!(registry.promo_redemptions(), 2);
+    client.request_guest_refund(&String::from_str(&env, "p0"));
+    assert_eq!(registry.promo_redemptions(), 0);
+}
+
+#[test]
+fn test_max_per_buyer_limits_and_refund_credit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _organizer, usdc_id, registry) = setup_cancellable(&env);
+    registry.set_max_per_buyer(&3);
+    let event_id = String::from_str(&env, "event_1");
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &1_000_000);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &1_000_000, &99999);
+
+    let pay = |id: &str, tier_id: &str, quantity: u32| {
+        client.try_process_payment(
+            &String::from_str(&env, id),
+            &event_id,
+            &String::from_str(&env, tier_id),
+            &buyer,
+            &usdc_id,
+            &(10000 * quantity as i128),
+            &quantity,
+            &None,
+            &None,
+        )
+    };
+
+    // Tier limit of 2
+    pay("p0", "limited", 2).unwrap().unwrap();
+    let result = pay("p1", "limited", 1);
+    assert_eq!(result, Err(Ok(TicketPaymentError::BuyerLimitExceeded)));
+
+    // Event limit of 3 across tiers
+    pay("p1", "t1", 1).unwrap().unwrap();
+    let result = pay("p2", "t1", 1);
+    assert_eq!(result, Err(Ok(TicketPaymentError::BuyerLimitExceeded)));
+
+    // Other buyers are unaffected
+    let other = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&other, &20000);
+    token::Client::new(&env, &usdc_id).approve(&other, &client.address, &20000, &99999);
+    client.process_payment(
+        &String::from_str(&env, "o0"),
+        &event_id,
+        &String::from_str(&env, "limited"),
+        &other,
+        &usdc_id,
+        &20000,
+        &2,
+        &None,
+        &None,
+    );
+
+    // A refund credits the tickets back to the buyer's counts
+    client.request_guest_refund(&String::from_str(&env, "p0"));
+    let purchases = client.get_buyer_purchases(&event_id, &buyer);
+    assert_eq!(purchases.total, 1);
+    assert_eq!(
+        purchases
+            .tiers
+            .get(String::from_str(&env, "limited"))
+            .unwrap(),
+        0
+    );
+    pay("p2", "limited", 2).unwrap().unwrap();
+    assert_eq!(client.get_buyer_purchases(&event_id, &buyer).total, 3);
+}
+
+#[test]
+fn test_gated_tier_access_rules() {
+    use soroban_sdk::xdr::ToXdr;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _organizer, usdc_id, registry) = setup_cancellable(&env);
+    let buyer = Address::generate(&env);
+    let other = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &100000);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &100000, &99999);
+
+    let pay = |id: &str, proof: &Option<soroban_sdk::Vec<soroban_sdk::BytesN<32>>>| {
+        client.try_process_payment(
+            &String::from_str(&env, id),
+            &String::from_str(&env, "event_1"),
+            &String::from_str(&env, "t1"),
+            &buyer,
+            &usdc_id,
+            &10000,
+            &1,
+            &None,
+            proof,
+        )
+    };
+
+    // Allowlist kept in the registry
+    registry.set_access_rule(&event_registry::AccessRule::Allowlist);
+    assert_eq!(
+        pay("p0", &None),
+        Err(Ok(TicketPaymentError::TierAccessDenied))
+    );
+    registry.allow(&buyer);
+    pay("p0", &None).unwrap().unwrap();
+
+    // Merkle root over two leaves; the buyer's proof is the other leaf
+    let leaf = |account: &Address| -> soroban_sdk::BytesN<32> {
+        env.crypto().sha256(&account.clone().to_xdr(&env)).into()
+    };
+    let (buyer_leaf, other_leaf) = (leaf(&buyer), leaf(&other));
+    let mut pair = soroban_sdk::Bytes::new(&env);
+    if buyer_leaf <= other_leaf {
+        pair.append(&buyer_leaf.clone().into());
+        pair.append(&other_leaf.clone().into());
+    } else {
+        pair.append(&other_leaf.clone().into());
+        pair.append(&buyer_leaf.clone().into());
+    }
+    let root: soroban_sdk::BytesN<32> = env.crypto().sha256(&pair).into();
+    registry.set_access_rule(&event_registry::AccessRule::MerkleRoot(root));
+
+    assert_eq!(
+        pay("p1", &None),
+        Err(Ok(TicketPaymentError::TierAccessDenied))
+    );
+    let wrong_proof = soroban_sdk::vec![&env, buyer_leaf];
+    assert_eq!(
+        pay("p1", &Some(wrong_proof)),
+        Err(Ok(TicketPaymentError::TierAccessDenied))
+    );
+    pay("p1", &Some(soroban_sdk::vec![&env, other_leaf]))
+        .unwrap()
+        .unwrap();
+
+    // Minimum token balance
+    let member_token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    registry.set_access_rule(&event_registry::AccessRule::TokenHolder(
+        member_token.clone(),
+        5,
+    ));
+    assert_eq!(
+        pay("p2", &None),
+        Err(Ok(TicketPaymentError::TierAccessDenied))
+    );
+    token::StellarAssetClient::new(&env, &member_token).mint(&buyer, &5);
+    pay("p2", &None).unwrap().unwrap();
+}
+
+#[test]
+fn test_cancellation_refunds_current_ticket_holders() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, organizer, usdc_id, registry) = setup_cancellable(&env);
+    let usdc = token::Client::new(&env, &usdc_id);
+    let buyer = Address::generate(&env);
+    let amount = 3000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    usdc.approve(&buyer, &client.address, &amount, &99999);
+    client.process_payment(
+        &String::from_str(&env, "p0"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &usdc_id,
+        &amount,
+        &3,
+        &None,
+        &None,
+    );
+
+    // Ticket 1 is given away and ticket 2 is resold; the buyer keeps ticket 3
+    let friend = Address::generate(&env);
+    client.transfer_ticket(&1, &friend);
+    let price = 1100_0000000i128;
+    client.list_ticket(&2, &price, &usdc_id);
+    let reseller_buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&reseller_buyer, &price);
+    usdc.approve(&reseller_buyer, &client.address, &price, &99999);
+    client.buy_listing(&2, &reseller_buyer);
+    let buyer_before = usdc.balance(&buyer);
+
+    registry.set_status(&event_registry::EventStatus::Cancelled);
+    let result = client.refund_cancelled_event(&organizer, &String::from_str(&env, "event_1"), &10);
+    assert_eq!(result.refunded, 1);
+    assert_eq!(result.amount_refunded, amount);
+
+    // Each holder gets the face value of the ticket they hold
+    assert_eq!(usdc.balance(&friend), 1000_0000000);
+    assert_eq!(usdc.balance(&reseller_buyer), 1000_0000000);
+    assert_eq!(usdc.balance(&buyer) - buyer_before, 1000_0000000);
+    assert_eq!(client.tickets_of(&friend).len(), 0);
+    assert_eq!(client.tickets_of(&reseller_buyer).len(), 0);
+    assert_eq!(
+        client.get_ticket(&1).unwrap().status,
+        TicketStatus::Refunded
+    );
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, BytesN, Map, String};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -15,14 +15,50 @@ pub struct Payment {
     pub payment_id: String,
     pub event_id: String,
     pub buyer_address: Address,
+    pub token_address: Address, // Token the payment was made in
     pub ticket_tier_id: String,
-    pub amount: i128, // USDC amount in stroops
+    pub tier_price: i128, // Tier price applied at purchase time, before any promo discount
+    pub quantity: u32,    // Number of tickets covered by this payment
+    pub amount: i128,     // USDC amount in stroops
     pub platform_fee: i128,
     pub organizer_amount: i128,
+    pub refunded_amount: i128, // Amount paid back to the buyer on refund
     pub status: PaymentStatus,
     pub transaction_hash: String,
     pub created_at: u64,
     pub confirmed_at: Option<u64>,
+    pub promo_code_hash: Option<BytesN<32>>, // SHA-256 of the promo code redeemed, if any
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TicketStatus {
+    Active,
+    Refunded,
+    CheckedIn,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ticket {
+    pub ticket_id: u64,
+    pub payment_id: String, // Payment the ticket was bought with
+    pub event_id: String,
+    pub ticket_tier_id: String,
+    pub owner: Address,
+    pub status: TicketStatus,
+    pub issued_at: u64,
+    pub checked_in_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing {
+    pub ticket_id: u64,
+    pub seller: Address,
+    pub price: i128,            // Asking price in stroops
+    pub token_address: Address, // Token the buyer pays in
+    pub listed_at: u64,
 }
 
 #[contracttype]
@@ -32,16 +68,49 @@ pub struct EventBalance {
     pub platform_fee: i128,
 }
 
+/// Tickets a buyer holds from primary sales of one event, refunds excluded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuyerPurchases {
+    pub total: u32,              // Tickets across all tiers
+    pub tiers: Map<String, u32>, // tier_id -> tickets in that tier
+}
+
+/// Progress of a paged cancellation refund run.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundBatchResult {
+    pub processed: u32,        // Payments visited in this batch
+    pub refunded: u32,         // Payments refunded in this batch
+    pub skipped: u32,          // Eligible payments whose escrow was already withdrawn
+    pub amount_refunded: i128, // Sum refunded in this batch, across tokens
+    pub next_cursor: u32,      // Index into the event's payment list to resume from
+    pub total_payments: u32,   // Length of the event's payment list
+    pub completed: bool,       // True once every payment has been visited
+}
+
 #[contracttype]
 pub enum DataKey {
-    Payment(String),         // payment_id -> Payment
-    EventPayments(String),   // event_id -> Vec<payment_id>
-    BuyerPayments(Address),  // buyer_address -> Vec<payment_id>
-    Admin,                   // Contract administrator address
-    UsdcToken,               // USDC token address
-    PlatformWallet,          // Platform wallet address
-    EventRegistry,           // Event Registry contract address
-    Initialized,             // Initialization flag
-    TokenWhitelist(Address), // token_address -> bool
-    Balances(String),        // event_id -> EventBalance (escrow tracking)
+    Payment(String),                 // payment_id -> Payment
+    EventPayments(String),           // event_id -> Vec<payment_id>
+    BuyerPayments(Address),          // buyer_address -> Vec<payment_id>
+    Admin,                           // Contract administrator address
+    UsdcToken,                       // USDC token address
+    PlatformWallet,                  // Platform wallet address
+    EventRegistry,                   // Event Registry contract address
+    Initialized,                     // Initialization flag
+    TokenWhitelist(Address),         // token_address -> bool
+    Balances(String, Address),       // (event_id, token) -> EventBalance (escrow tracking)
+    EventTokens(String),             // event_id -> Vec<token_address> with escrow balances
+    RefundCursor(String), // event_id -> next index into EventPayments for cancellation refunds
+    ReleaseHoldPeriod,    // Seconds organizer funds stay escrowed after an event ends
+    TrustedOrganizer(Address), // organizer_address -> bool (may withdraw before unlock)
+    TicketCounter,        // Last issued ticket_id
+    Ticket(u64),          // ticket_id -> Ticket
+    OwnerTickets(Address), // owner_address -> Vec<ticket_id>
+    PaymentTickets(String), // payment_id -> Vec<ticket_id>
+    Listing(u64),         // ticket_id -> Listing (resale marketplace)
+    PendingAdmin,         // Administrator proposed by the current one, waiting to accept
+    Paused,               // Emergency pause flag
+    BuyerPurchases(String, Address), // (event_id, buyer) -> BuyerPurchases (per-buyer limits)
 }